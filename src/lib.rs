@@ -1,15 +1,26 @@
 extern crate cfg_if;
+extern crate js_sys;
 extern crate wasm_bindgen;
 extern crate web_sys;
 
+mod base64;
+mod pattern;
+mod rle;
+mod rng;
+mod rule;
 mod utils;
 
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 
 use cfg_if::cfg_if;
 use wasm_bindgen::prelude::*;
 use web_sys::console;
 
+use rng::Rng;
+use rule::Rule;
+
 cfg_if! {
     // When the `wee_alloc` feature is enabled, use `wee_alloc` as the global
     // allocator.
@@ -17,6 +28,9 @@ cfg_if! {
         extern crate wee_alloc;
         #[global_allocator]
         static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
+        const ALLOCATOR_NAME: &str = "wee_alloc";
+    } else {
+        const ALLOCATOR_NAME: &str = "system";
     }
 }
 
@@ -26,20 +40,24 @@ pub struct Timer<'a> {
 
 impl<'a> Timer<'a> {
     pub fn new(name: &'a str) -> Timer<'a> {
-        console::time_with_label(name);
+        if cfg!(target_arch = "wasm32") {
+            console::time_with_label(name);
+        }
         Timer { name }
     }
 }
 
 impl<'a> Drop for Timer<'a> {
     fn drop(&mut self) {
-        console::time_end_with_label(self.name);
+        if cfg!(target_arch = "wasm32") {
+            console::time_end_with_label(self.name);
+        }
     }
 }
 
 #[wasm_bindgen]
 #[repr(u8)]
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum Cell {
     Dead = 0,
     Alive = 1,
@@ -54,11 +72,231 @@ impl Cell {
     }
 }
 
+/// What lies past the edges of the universe.
+#[wasm_bindgen]
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Boundary {
+    /// The edges wrap around, so the universe is a torus.
+    Toroidal = 0,
+    /// Everything past the edges is dead.
+    Dead = 1,
+}
+
+/// A direction towards one of the edges of the universe.
+#[wasm_bindgen]
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Up = 0,
+    Down = 1,
+    Left = 2,
+    Right = 3,
+}
+
+/// A symmetry of the live cells, as a flag in `Universe::symmetry_class`.
+#[wasm_bindgen]
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Symmetry {
+    /// Mirrored top to bottom, across a horizontal axis.
+    Horizontal = 1,
+    /// Mirrored left to right, across a vertical axis.
+    Vertical = 2,
+    /// Unchanged by a half turn.
+    Rotate180 = 4,
+    /// Unchanged by a quarter turn.
+    Rotate90 = 8,
+    /// Mirrored across the diagonal from top left to bottom right.
+    Diagonal = 16,
+    /// Mirrored across the diagonal from top right to bottom left.
+    AntiDiagonal = 32,
+}
+
+/// How `Universe::tick` applies the rule to the cells.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UpdateMode {
+    /// Every cell is updated at once from the previous generation. This is
+    /// classic Life.
+    Synchronous,
+    /// Cells are updated one at a time in a random order, each reading the
+    /// partially updated board.
+    ///
+    /// This breaks the guarantees of classic Life: well-known oscillators and
+    /// spaceships generally fall apart. The order is drawn from a generator
+    /// seeded with `seed`, so runs are reproducible.
+    RandomAsync { seed: u64 },
+    /// Every cell is updated at once, then flipped if it was alive the
+    /// generation before.
+    ///
+    /// This second-order form makes any rule reversible: the previous
+    /// generation can always be worked out again, so `Universe::untick` can
+    /// step backwards.
+    SecondOrder,
+}
+
+/// The number of slots for `Universe::store_rule_preset`.
+const RULE_PRESET_SLOTS: usize = 4;
+
 #[wasm_bindgen]
+#[derive(Clone)]
 pub struct Universe {
     width: u32,
     height: u32,
     cells: Vec<Cell>,
+    update_mode: UpdateMode,
+    rng: Rng,
+    /// `(top, left, height, width)` of the area outside which cells are kept
+    /// dead, if any.
+    play_area: Option<(u32, u32, u32, u32)>,
+    /// The rules for cells where `row + column` is even and odd, respectively.
+    rules: [Rule; 2],
+    /// Whether `tick` appends its changes to `recording`.
+    is_recording: bool,
+    recording: Vec<u8>,
+    /// The cells as of the last frame added to `recording`.
+    recorded_frame: Vec<Cell>,
+    /// Saved `(width, height, cells)`, by name.
+    snapshots: HashMap<String, (u32, u32, Vec<Cell>)>,
+    boundary: Boundary,
+    /// `(top, left, height, width)` of a rectangle that wraps around on
+    /// itself, if any.
+    wrap_region: Option<(u32, u32, u32, u32)>,
+    /// How many rows or columns to add to an edge that live cells reach,
+    /// and how many more may be added in total.
+    growth_margin: u32,
+    growth_remaining: u32,
+    /// The next generation, as last computed by `precompute_next`.
+    next: Vec<Cell>,
+    rule_presets: [Option<Rule>; RULE_PRESET_SLOTS],
+    /// How many times `tick` has run.
+    generation: u32,
+    /// `(row, column, period, phase)` of cells forced alive on generations
+    /// that are `phase` modulo `period`.
+    pulsers: Vec<(u32, u32, u32, u32)>,
+    /// The cells as of the last `full_sync` or `delta_since_last_sync`.
+    sync_base: Vec<Cell>,
+    /// How many times each cell has changed state since the counts were
+    /// last reset.
+    flip_count: Vec<u32>,
+    /// Whether `tick` updates `flip_count`.
+    track_flips: bool,
+    /// How many ticks each live cell has survived since it came alive.
+    ages: Vec<u16>,
+    /// Whether `tick` updates `ages`.
+    track_ages: bool,
+    /// `(generation, rule)` pairs, by generation, of when to switch rules.
+    rule_schedule: Vec<(u32, Rule)>,
+    /// The rules to go back to before the first entry of `rule_schedule`.
+    unscheduled_rules: [Rule; 2],
+    /// The generation before the current one, for `UpdateMode::SecondOrder`.
+    previous: Vec<Cell>,
+    /// The rules `has_orphan_neighborhood` last ran under, with the 3x3
+    /// blocks they can produce on even and odd squares.
+    reachable_windows: Option<([Rule; 2], [Vec<bool>; 2])>,
+}
+
+/// Get the half-open range of source cells covered by output cell `index`
+/// when `source` cells are sampled down (or up) to `out` cells.
+fn block_span(index: u32, out: u32, source: u32) -> (u32, u32) {
+    let start = index * source / out;
+    let end = (index + 1) * source / out;
+    (start, end.max(start + 1).min(source))
+}
+
+/// Get the `x` that the midpoint circle algorithm picks for a given `y` in
+/// the octant of a circle of `radius` where `x >= y`, or `None` if it stops
+/// before reaching `y`.
+///
+/// The algorithm keeps the largest `x` with `x * (x - 1) + y * y` below
+/// `radius * radius`, which this finds directly instead of stepping there.
+fn midpoint_circle_x(radius: i64, y: i64) -> Option<i64> {
+    if radius == 0 {
+        return if y == 0 { Some(0) } else { None };
+    }
+    let room = i128::from(radius).pow(2) - i128::from(y).pow(2);
+    if room <= 0 {
+        return None;
+    }
+    let mut x = (room as u128).isqrt() as i128;
+    while (x + 1) * x < room {
+        x += 1;
+    }
+    while x * (x - 1) >= room {
+        x -= 1;
+    }
+    if x < i128::from(y) {
+        None
+    } else {
+        Some(x as i64)
+    }
+}
+
+/// Check whether a `(top, left, height, width)` rectangle contains a cell.
+fn rect_contains((top, left, height, width): (u32, u32, u32, u32), row: u32, column: u32) -> bool {
+    row >= top && row - top < height && column >= left && column - left < width
+}
+
+/// Escape a value for use inside a double-quoted XML attribute.
+fn escape_attribute(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn push_u32(bytes: &mut Vec<u8>, value: u32) {
+    bytes.extend_from_slice(&value.to_le_bytes());
+}
+
+fn push_u64(bytes: &mut Vec<u8>, value: u64) {
+    bytes.extend_from_slice(&value.to_le_bytes());
+}
+
+/// Append a run length for `Universe::to_rle_bytes`, splitting it into
+/// bytes.
+fn push_run(bytes: &mut Vec<u8>, mut run: u32) {
+    while run > 255 {
+        bytes.push(255);
+        bytes.push(0);
+        run -= 255;
+    }
+    bytes.push(run as u8);
+}
+
+/// Reads little-endian values from the front of a byte slice.
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> ByteReader<'a> {
+    fn new(bytes: &'a [u8]) -> ByteReader<'a> {
+        ByteReader { bytes }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    fn bytes(&mut self, len: usize) -> Option<&'a [u8]> {
+        if self.bytes.len() < len {
+            return None;
+        }
+        let (head, tail) = self.bytes.split_at(len);
+        self.bytes = tail;
+        Some(head)
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        self.bytes(4)
+            .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn u64(&mut self) -> Option<u64> {
+        self.bytes(8)
+            .map(|b| u64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]))
+    }
 }
 
 #[wasm_bindgen]
@@ -73,6 +311,7 @@ impl Universe {
     pub fn set_width(&mut self, width: u32) {
         self.width = width;
         self.cells = (0..width * self.height).map(|_i| Cell::Dead).collect();
+        self.fit_to_size();
     }
 
     pub fn height(&self) -> u32 {
@@ -85,6 +324,7 @@ impl Universe {
     pub fn set_height(&mut self, height: u32) {
         self.height = height;
         self.cells = (0..height * self.width).map(|_i| Cell::Dead).collect();
+        self.fit_to_size();
     }
 
     pub fn cells(&self) -> *const Cell {
@@ -93,27 +333,29 @@ impl Universe {
 
     pub fn tick(&mut self) {
         let _timer = Timer::new("Universe::tick");
-        let mut next = self.cells.clone();;
-
-        for row in 0..self.height {
-            for col in 0..self.width {
-                let idx = self.get_index(row, col);
-                let cell = self.cells[idx];
-                let live_neighbor_count = self.live_neighbor_count(row, col);
-
-                let next_cell = match (cell, live_neighbor_count) {
-                    (Cell::Alive, x) if x < 2 => Cell::Dead,
-                    (Cell::Alive, 2) | (Cell::Alive, 3) => Cell::Alive,
-                    (Cell::Alive, x) if x > 3 => Cell::Dead,
-                    (Cell::Dead, 3) => Cell::Alive,
-                    (otherwise, _) => otherwise,
-                };
-
-                next[idx] = next_cell;
-            }
+        let before = if self.track_flips || self.track_ages {
+            self.cells.clone()
+        } else {
+            Vec::new()
+        };
+        match self.update_mode {
+            UpdateMode::Synchronous => self.cells = self.next_generation(),
+            UpdateMode::RandomAsync { .. } => self.tick_random_async(),
+            UpdateMode::SecondOrder => self.tick_second_order(),
         }
-
-        self.cells = next;
+        self.generation += 1;
+        self.follow_rule_schedule();
+        self.fire_pulsers();
+        if self.track_flips {
+            self.count_flips(&before);
+        }
+        if self.track_ages {
+            self.age_cells(&before);
+        }
+        if self.is_recording {
+            self.record_changes();
+        }
+        self.grow_towards_live_edges();
     }
 
     pub fn new() -> Universe {
@@ -136,6 +378,31 @@ impl Universe {
             width,
             height,
             cells,
+            update_mode: UpdateMode::Synchronous,
+            rng: Rng::new(0),
+            play_area: None,
+            rules: [Rule::life(); 2],
+            is_recording: false,
+            recording: Vec::new(),
+            recorded_frame: Vec::new(),
+            snapshots: HashMap::new(),
+            boundary: Boundary::Toroidal,
+            wrap_region: None,
+            growth_margin: 0,
+            growth_remaining: 0,
+            next: Vec::new(),
+            rule_presets: [None; RULE_PRESET_SLOTS],
+            generation: 0,
+            pulsers: Vec::new(),
+            sync_base: Vec::new(),
+            flip_count: Vec::new(),
+            track_flips: false,
+            ages: Vec::new(),
+            track_ages: false,
+            rule_schedule: Vec::new(),
+            unscheduled_rules: [Rule::life(); 2],
+            previous: Vec::new(),
+            reachable_windows: None,
         }
     }
 
@@ -146,141 +413,5275 @@ impl Universe {
     pub fn toggle_cell(&mut self, row: u32, col: u32) {
         let idx = self.get_index(row, col);
         self.cells[idx].toggle();
+        self.forget_age(idx);
     }
 
-    fn get_index(&self, row: u32, column: u32) -> usize {
-        (row * self.width + column) as usize
+    /// Get the fraction of border cells that are alive.
+    ///
+    /// Corner cells are counted once.
+    pub fn edge_density(&self) -> f64 {
+        let mut border = 0;
+        let mut alive = 0;
+        for row in 0..self.height {
+            for col in 0..self.width {
+                if row != 0 && row != self.height - 1 && col != 0 && col != self.width - 1 {
+                    continue;
+                }
+                border += 1;
+                alive += self.cells[self.get_index(row, col)] as u32;
+            }
+        }
+        if border == 0 {
+            return 0.0;
+        }
+        alive as f64 / border as f64
     }
 
-    fn live_neighbor_count(&self, row: u32, column: u32) -> u8 {
-        let mut count = 0;
-        for delta_row in [self.height - 1, 0, 1].iter().cloned() {
-            for delta_col in [self.width - 1, 0, 1].iter().cloned() {
-                if delta_row == 0 && delta_col == 0 {
-                    continue;
+    /// Downsample the universe into a grayscale minimap.
+    ///
+    /// Each output byte is the live-cell density of the corresponding block
+    /// of cells, scaled to `0..=255`.
+    pub fn minimap(&self, out_width: u32, out_height: u32) -> Vec<u8> {
+        let mut pixels = Vec::with_capacity((out_width * out_height) as usize);
+        for out_row in 0..out_height {
+            let (row_start, row_end) = block_span(out_row, out_height, self.height);
+            for out_col in 0..out_width {
+                let (col_start, col_end) = block_span(out_col, out_width, self.width);
+                let mut alive = 0;
+                for row in row_start..row_end {
+                    for col in col_start..col_end {
+                        alive += self.cells[self.get_index(row, col)] as u32;
+                    }
                 }
+                let total = (row_end - row_start) * (col_end - col_start);
+                let value = (alive * 255 + total / 2).checked_div(total).unwrap_or(0);
+                pixels.push(value as u8);
+            }
+        }
+        pixels
+    }
 
-                let neighbor_row = (row + delta_row) % self.height;
-                let neighbor_col = (column + delta_col) % self.width;
-                let idx = self.get_index(neighbor_row, neighbor_col);
-                count += self.cells[idx] as u8;
+    /// Get the indices of cells that are alive in some, but not all, of the
+    /// next `period` generations (the current one included).
+    ///
+    /// The universe is restored afterwards.
+    pub fn transient_cells(&mut self, period: u32) -> Vec<u32> {
+        let saved = self.clone();
+        let mut ever_alive = vec![false; self.cells.len()];
+        let mut always_alive = vec![true; self.cells.len()];
+        for phase in 0..period {
+            if phase > 0 {
+                self.tick();
+            }
+            for (idx, &cell) in self.cells.iter().enumerate() {
+                ever_alive[idx] |= cell == Cell::Alive;
+                always_alive[idx] &= cell == Cell::Alive;
             }
         }
-        count
+        *self = saved;
+
+        (0..self.cells.len())
+            .filter(|&idx| ever_alive[idx] && !always_alive[idx])
+            .map(|idx| idx as u32)
+            .collect()
     }
-}
 
-impl Universe {
-    /// Get the dead and alive state of the entire universe.
-    pub fn get_cells(&self) -> &[Cell] {
-        &self.cells
+    /// Get the area of the convex hull around the live cells, treating each
+    /// cell as a point at its `(row, column)`.
+    ///
+    /// Returns 0 when the live cells are fewer than three or collinear.
+    pub fn hull_area(&self) -> f64 {
+        let mut points: Vec<(i64, i64)> = self
+            .live_coordinates()
+            .into_iter()
+            .map(|(row, col)| (col as i64, row as i64))
+            .collect();
+        points.sort();
+
+        fn cross(o: (i64, i64), a: (i64, i64), b: (i64, i64)) -> i64 {
+            (a.0 - o.0) * (b.1 - o.1) - (a.1 - o.1) * (b.0 - o.0)
+        }
+
+        // Andrew's monotone chain: build the lower and upper hulls.
+        let mut hull: Vec<(i64, i64)> = Vec::with_capacity(points.len() * 2);
+        for pass in 0..2 {
+            let start = hull.len();
+            let ordered: Vec<(i64, i64)> = if pass == 0 {
+                points.clone()
+            } else {
+                points.iter().rev().cloned().collect()
+            };
+            for p in ordered {
+                while hull.len() >= start + 2
+                    && cross(hull[hull.len() - 2], hull[hull.len() - 1], p) <= 0
+                {
+                    hull.pop();
+                }
+                hull.push(p);
+            }
+            hull.pop();
+        }
+
+        if hull.len() < 3 {
+            return 0.0;
+        }
+        let twice_area: i64 = (0..hull.len())
+            .map(|i| {
+                let (a, b) = (hull[i], hull[(i + 1) % hull.len()]);
+                a.0 * b.1 - b.0 * a.1
+            })
+            .sum();
+        twice_area.abs() as f64 / 2.0
     }
 
-    /// Set cells to be alive in a universe by passing the row and column
-    /// of each cell as an array.
-    pub fn set_cells(&mut self, cells: &[(u32, u32)]) {
-        for (row, col) in cells.iter().cloned() {
-            let idx = self.get_index(row, col);
-            self.cells[idx] = Cell::Alive;
+    /// Render the universe into RGBA pixels, `cell_size` pixels per cell.
+    ///
+    /// Colors are packed as `0xRRGGBBAA`. The image is
+    /// `width * cell_size` pixels wide and `height * cell_size` pixels high.
+    pub fn render_rgba(&self, cell_size: u32, alive: u32, dead: u32) -> Vec<u8> {
+        let image_width = self.width * cell_size;
+        let image_height = self.height * cell_size;
+        let mut pixels = Vec::with_capacity((image_width * image_height * 4) as usize);
+        for y in 0..image_height {
+            for x in 0..image_width {
+                let cell = self.cells[self.get_index(y / cell_size, x / cell_size)];
+                let color = if cell == Cell::Alive { alive } else { dead };
+                pixels.extend_from_slice(&color.to_be_bytes());
+            }
         }
+        pixels
     }
-}
 
-impl fmt::Display for Universe {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        for line in self.cells.as_slice().chunks(self.width as usize) {
-            for &cell in line {
-                let symbol = if cell == Cell::Dead { '◻' } else { '◼' };
-                write!(f, "{}", symbol)?;
+    /// Render `frames` successive generations side by side into one RGBA
+    /// image, separated by `gap` transparent pixels.
+    ///
+    /// The universe is restored afterwards.
+    pub fn filmstrip_rgba(
+        &mut self,
+        frames: u32,
+        cell_size: u32,
+        gap: u32,
+        alive: u32,
+        dead: u32,
+    ) -> Vec<u8> {
+        if frames == 0 {
+            return Vec::new();
+        }
+        let saved = self.clone();
+        let frame_width = (self.width * cell_size) as usize;
+        let frame_height = (self.height * cell_size) as usize;
+        let strip_width = frames as usize * (frame_width + gap as usize) - gap as usize;
+        let mut pixels = vec![0; strip_width * frame_height * 4];
+        for frame in 0..frames as usize {
+            if frame > 0 {
+                self.tick();
+            }
+            let image = self.render_rgba(cell_size, alive, dead);
+            let left = frame * (frame_width + gap as usize);
+            for y in 0..frame_height {
+                let src = y * frame_width * 4;
+                let dst = (y * strip_width + left) * 4;
+                let len = frame_width * 4;
+                pixels[dst..dst + len].copy_from_slice(&image[src..src + len]);
             }
-            write!(f, "\n")?;
         }
+        *self = saved;
+        pixels
+    }
 
-        Ok(())
+    /// Settle the universe, then count the distinct shapes among its
+    /// connected groups of live cells, ignoring position and orientation.
+    ///
+    /// The universe is settled once it repeats an earlier state, or after
+    /// `max_generations` ticks. It is restored afterwards.
+    pub fn settle_species(&mut self, max_generations: u32) -> u32 {
+        let saved = self.clone();
+        self.settle(max_generations);
+        let species: HashSet<Vec<(i64, i64)>> = self
+            .components()
+            .iter()
+            .map(|component| pattern::canonical(component))
+            .collect();
+        *self = saved;
+        species.len() as u32
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    fn get_init_universe() -> Universe {
+    /// Restrict life to a rectangle of the universe.
+    ///
+    /// On every tick, cells outside the rectangle die, and cells inside see
+    /// them as dead neighbors. A rectangle covering the whole
+    /// universe removes the restriction.
+    pub fn set_play_area(&mut self, top: u32, left: u32, height: u32, width: u32) {
+        self.play_area = if top == 0 && left == 0 && height >= self.height && width >= self.width {
+            None
+        } else {
+            Some((top, left, height, width))
+        };
+    }
+
+    /// Use `rule_a` for cells where `row + column` is even and `rule_b` where
+    /// it is odd, both in B/S notation such as `B3/S23`.
+    ///
+    /// The rules are left unchanged if either one is invalid. Otherwise any
+    /// rule schedule is dropped, so that it doesn't switch back.
+    pub fn set_rule_checkerboard(&mut self, rule_a: &str, rule_b: &str) {
+        if let (Ok(rule_a), Ok(rule_b)) = (Rule::parse(rule_a), Rule::parse(rule_b)) {
+            self.rules = [rule_a, rule_b];
+            self.rule_schedule.clear();
+        }
+    }
 
-        let mut cells: Vec<Cell> = vec![Cell::Dead; 25];
-        cells[6] = Cell::Alive;
-        cells[11] = Cell::Alive;
-        cells[16] = Cell::Alive;
+    /// Render each row of the universe as a string, using `alive` and `dead`
+    /// for the cells.
+    pub fn render_rows(&self, alive: char, dead: char) -> Vec<String> {
+        self.cells
+            .chunks(self.width as usize)
+            .map(|line| {
+                line.iter()
+                    .map(|&cell| if cell == Cell::Alive { alive } else { dead })
+                    .collect()
+            })
+            .collect()
+    }
 
-        let universe = Universe {
-            width: 5,
-            height: 5,
-            cells,
+    /// Get a byte per cell that is 1 where the cell belongs to a glider and 0
+    /// elsewhere.
+    ///
+    /// Gliders are found in any phase and orientation, but only when nothing
+    /// else is alive right next to them.
+    pub fn glider_mask(&self) -> Vec<u8> {
+        let mut mask = vec![0; self.cells.len()];
+        for glider in self.find_gliders() {
+            for idx in glider {
+                mask[idx] = 1;
+            }
+        }
+        mask
+    }
+
+    /// Start recording the run, discarding any earlier recording.
+    ///
+    /// The recording starts with the current state, and every `tick` adds the
+    /// cells that changed since the last frame, including any edits made in
+    /// between. Recording stops if the universe changes size. See
+    /// `recording_bytes` for the format.
+    pub fn start_recording(&mut self) {
+        self.is_recording = true;
+        self.recording.clear();
+        push_u32(&mut self.recording, self.width);
+        push_u32(&mut self.recording, self.height);
+        self.recording
+            .extend(self.cells.iter().map(|&cell| cell as u8));
+        self.recorded_frame = self.cells.clone();
+    }
+
+    /// Stop adding ticks to the recording. The recording is kept.
+    pub fn stop_recording(&mut self) {
+        self.is_recording = false;
+        self.recorded_frame = Vec::new();
+    }
+
+    /// Get the recording of the run.
+    ///
+    /// All numbers are little-endian `u32`s. The stream is the width and the
+    /// height, then one byte per cell (0 dead, 1 alive) for the starting
+    /// state, then for each tick the number of changed cells followed by
+    /// their indices.
+    pub fn recording_bytes(&self) -> Vec<u8> {
+        self.recording.clone()
+    }
+
+    /// Replace the universe with the last frame of a recording made by
+    /// `recording_bytes`.
+    ///
+    /// Returns false, leaving the universe unchanged, if the recording is
+    /// malformed.
+    pub fn replay_recording(&mut self, bytes: &[u8]) -> bool {
+        let mut reader = ByteReader::new(bytes);
+        let (width, height) = match (reader.u32(), reader.u32()) {
+            (Some(width), Some(height)) => (width, height),
+            _ => return false,
         };
-        universe
+        let size = match width.checked_mul(height) {
+            Some(size) => size as usize,
+            None => return false,
+        };
+        let mut cells: Vec<Cell> = match reader.bytes(size) {
+            Some(start) => start
+                .iter()
+                .map(|&b| if b == 0 { Cell::Dead } else { Cell::Alive })
+                .collect(),
+            None => return false,
+        };
+        while !reader.is_empty() {
+            let count = match reader.u32() {
+                Some(count) => count,
+                None => return false,
+            };
+            for _ in 0..count {
+                match reader.u32() {
+                    Some(idx) if (idx as usize) < size => cells[idx as usize].toggle(),
+                    _ => return false,
+                }
+            }
+        }
+        self.width = width;
+        self.height = height;
+        self.cells = cells;
+        self.fit_to_size();
+        true
     }
 
-    #[test]
-    fn it_should_work() {
-        assert_eq!(0, 0);
+    /// Get how well the universe matches itself shifted by
+    /// `(shift_row, shift_col)`.
+    ///
+    /// This is the fraction of cells whose state equals the shifted cell's,
+    /// minus the fraction that differ: 1 for a perfect match and -1 for a
+    /// perfect mismatch. Cells shifted past an edge wrap around under
+    /// `Boundary::Toroidal` and are dead under `Boundary::Dead`.
+    pub fn spatial_autocorr(&self, shift_row: i32, shift_col: i32) -> f64 {
+        if self.cells.is_empty() {
+            return 0.0;
+        }
+        let mut score: i64 = 0;
+        for row in 0..self.height {
+            for col in 0..self.width {
+                let cell = self.cells[self.get_index(row, col)];
+                let shifted = match self.offset(row, col, shift_row as i64, shift_col as i64) {
+                    Some((r, c)) => self.cells[self.get_index(r, c)],
+                    None => Cell::Dead,
+                };
+                score += if cell == shifted { 1 } else { -1 };
+            }
+        }
+        score as f64 / self.cells.len() as f64
     }
 
-    #[test]
-    fn it_should_get_index() {
-        let uni = get_init_universe();
-        assert_eq!(uni.get_index(1, 1), 6);
-        assert_eq!(uni.get_index(2, 1), 11);
-        assert_eq!(uni.get_index(3, 1), 16);
+    /// Get the position of the random number generator used by
+    /// `UpdateMode::RandomAsync`.
+    pub fn rng_state(&self) -> u64 {
+        self.rng.state()
     }
 
-    #[test]
-    fn it_should_count_alive_neighbors() {
-        let uni = get_init_universe();
-        assert_eq!(uni.live_neighbor_count(0, 0), 1);
-        assert_eq!(uni.live_neighbor_count(0, 1), 1);
-        assert_eq!(uni.live_neighbor_count(1, 0), 2);
-        assert_eq!(uni.live_neighbor_count(2, 1), 2);
-        assert_eq!(uni.live_neighbor_count(2, 0), 3);
-        assert_eq!(uni.live_neighbor_count(2, 2), 3);
-        assert_eq!(uni.live_neighbor_count(3, 3), 0);
-        assert_eq!(uni.live_neighbor_count(4, 1), 1);
+    /// Move the random number generator to a position saved by `rng_state`.
+    pub fn set_rng_state(&mut self, state: u64) {
+        self.rng = Rng::new(state);
     }
 
-    #[test]
-    fn it_should_change_after_tick() {
-        let mut uni = get_init_universe();
-        assert_eq!(uni.cells[uni.get_index(1, 1)], Cell::Alive);
-        assert_eq!(uni.cells[uni.get_index(2, 1)], Cell::Alive);
-        assert_eq!(uni.cells[uni.get_index(3, 1)], Cell::Alive);
-        assert_eq!(uni.cells[uni.get_index(2, 0)], Cell::Dead);
-        assert_eq!(uni.cells[uni.get_index(2, 2)], Cell::Dead);
-        assert_eq!(uni.cells[uni.get_index(4, 2)], Cell::Dead);
-        uni.tick();
-        assert_eq!(uni.cells[uni.get_index(1, 1)], Cell::Dead);
-        assert_eq!(uni.cells[uni.get_index(2, 1)], Cell::Alive);
-        assert_eq!(uni.cells[uni.get_index(3, 1)], Cell::Dead);
-        assert_eq!(uni.cells[uni.get_index(2, 0)], Cell::Alive);
-        assert_eq!(uni.cells[uni.get_index(2, 2)], Cell::Alive);
-        assert_eq!(uni.cells[uni.get_index(4, 2)], Cell::Dead);
+    /// Serialize the cells, the update mode and the random number generator
+    /// position.
+    ///
+    /// All numbers are little-endian. The layout is the width and the height
+    /// as `u32`s, an update mode byte (0 synchronous, 1 random asynchronous,
+    /// 2 second order), the asynchronous seed and the generator position as
+    /// `u64`s, then one byte per cell (0 dead, 1 alive). In second-order
+    /// mode, one more byte per cell gives the previous generation.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(25 + 2 * self.cells.len());
+        push_u32(&mut bytes, self.width);
+        push_u32(&mut bytes, self.height);
+        let (mode, seed) = match self.update_mode {
+            UpdateMode::Synchronous => (0, 0),
+            UpdateMode::RandomAsync { seed } => (1, seed),
+            UpdateMode::SecondOrder => (2, 0),
+        };
+        bytes.push(mode);
+        push_u64(&mut bytes, seed);
+        push_u64(&mut bytes, self.rng.state());
+        bytes.extend(self.cells.iter().map(|&cell| cell as u8));
+        if self.update_mode == UpdateMode::SecondOrder {
+            if self.previous.len() == self.cells.len() {
+                bytes.extend(self.previous.iter().map(|&cell| cell as u8));
+            } else {
+                bytes.resize(bytes.len() + self.cells.len(), 0);
+            }
+        }
+        bytes
     }
 
-    #[test]
-    fn it_should_render() {
-        let uni = get_init_universe();
-        let mut expected_str = String::from("");
-        for row in 0..5 {
-            for col in 0..5 {
-                match (row, col) {
-                    (1,1)|(2,1)|(3,1) => {
-                        expected_str.push('◼');
-                    },
-                    _ => {
-                        expected_str.push('◻');
-                    },
+    /// Deserialize a universe written by `to_bytes`, or get `None` if the
+    /// bytes are malformed.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Universe> {
+        let mut reader = ByteReader::new(bytes);
+        let width = reader.u32()?;
+        let height = reader.u32()?;
+        let update_mode = match (reader.bytes(1)?[0], reader.u64()?) {
+            (0, _) => UpdateMode::Synchronous,
+            (1, seed) => UpdateMode::RandomAsync { seed },
+            (2, _) => UpdateMode::SecondOrder,
+            _ => return None,
+        };
+        let rng = Rng::new(reader.u64()?);
+        let len = width.checked_mul(height)? as usize;
+        let cells = reader.bytes(len)?;
+        let previous = if update_mode == UpdateMode::SecondOrder {
+            reader.bytes(len)?
+        } else {
+            &[]
+        };
+        if !reader.is_empty() {
+            return None;
+        }
+
+        let to_cells = |bytes: &[u8]| -> Vec<Cell> {
+            bytes
+                .iter()
+                .map(|&b| if b == 0 { Cell::Dead } else { Cell::Alive })
+                .collect()
+        };
+        let mut universe = Universe::new();
+        universe.width = width;
+        universe.height = height;
+        universe.cells = to_cells(cells);
+        universe.previous = to_cells(previous);
+        universe.update_mode = update_mode;
+        universe.rng = rng;
+        Some(universe)
+    }
+
+    /// Bring a well-known pattern to life with its top-left corner at
+    /// `(row, col)`, wrapping around the edges.
+    ///
+    /// Known names are `block`, `beehive`, `blinker`, `toad`, `beacon`,
+    /// `glider`, `lwss`, `r-pentomino` and `gosper-glider-gun`. Returns false
+    /// for any other name, or if the universe has no cells.
+    pub fn spawn_named(&mut self, name: &str, row: u32, col: u32) -> bool {
+        let cells = match pattern::named(name) {
+            Some(cells) if self.width > 0 && self.height > 0 => cells,
+            _ => return false,
+        };
+        for &(delta_row, delta_col) in cells {
+            let idx = self.get_index(
+                ((u64::from(row) + u64::from(delta_row)) % u64::from(self.height)) as u32,
+                ((u64::from(col) + u64::from(delta_col)) % u64::from(self.width)) as u32,
+            );
+            self.cells[idx] = Cell::Alive;
+            self.forget_age(idx);
+        }
+        true
+    }
+
+    /// Spawn several named patterns, given as `name,row,col` entries
+    /// separated by newlines or semicolons, e.g. `glider,0,0;block,10,10`.
+    ///
+    /// Entries that are malformed or name an unknown pattern are skipped.
+    /// Returns the number of patterns placed.
+    pub fn compose(&mut self, placements: &str) -> u32 {
+        let mut placed = 0;
+        for entry in placements.split(&['\n', ';'][..]) {
+            let fields: Vec<&str> = entry.split(',').map(str::trim).collect();
+            if let [name, row, col] = fields[..] {
+                if let (Ok(row), Ok(col)) = (row.parse(), col.parse()) {
+                    if self.spawn_named(name, row, col) {
+                        placed += 1;
+                    }
                 }
-                if col==4{
-                    expected_str.push('\n');
+            }
+        }
+        placed
+    }
+
+    /// Get, for each cell, a `(drow, dcol)` pair of signed bytes pointing
+    /// towards where live cells are denser within `radius` cells, wrapping
+    /// around the edges.
+    ///
+    /// Each vector is the sum of the offsets to the live cells in the
+    /// surrounding square, scaled so the longest component on the board is
+    /// 127. Cells with no live cells around them get `(0, 0)`.
+    pub fn density_gradient(&self, radius: u32) -> Vec<i8> {
+        let radius = radius as i64;
+        let mut sums = Vec::with_capacity(self.cells.len() * 2);
+        for row in 0..self.height as i64 {
+            for col in 0..self.width as i64 {
+                let (mut drow, mut dcol) = (0i64, 0i64);
+                for delta_row in -radius..=radius {
+                    for delta_col in -radius..=radius {
+                        let r = (row + delta_row).rem_euclid(self.height as i64);
+                        let c = (col + delta_col).rem_euclid(self.width as i64);
+                        if self.cells[self.get_index(r as u32, c as u32)] == Cell::Alive {
+                            drow += delta_row;
+                            dcol += delta_col;
+                        }
+                    }
                 }
+                sums.push(drow);
+                sums.push(dcol);
             }
         }
-        assert_eq!(uni.to_string(), expected_str);
+        let max = sums.iter().map(|v| v.abs()).max().unwrap_or(0).max(1);
+        sums.iter().map(|&v| (v * 127 / max) as i8).collect()
+    }
+
+    /// Check whether the cells starting at `(row, col)` exactly match a
+    /// pattern, wrapping around the edges.
+    ///
+    /// The pattern is given as one byte per cell (0 dead, anything else
+    /// alive), row by row, `pattern_width` cells per row. Only an empty
+    /// pattern matches a universe with no cells.
+    pub fn matches_pattern_at(
+        &self,
+        row: u32,
+        col: u32,
+        pattern_width: u32,
+        pattern: &[u8],
+    ) -> bool {
+        if pattern_width == 0 || self.width == 0 || self.height == 0 {
+            return pattern.is_empty();
+        }
+        pattern.iter().enumerate().all(|(i, &expected)| {
+            let (delta_row, delta_col) = (
+                i as u64 / u64::from(pattern_width),
+                i as u64 % u64::from(pattern_width),
+            );
+            let r = ((u64::from(row) + delta_row) % u64::from(self.height)) as u32;
+            let c = ((u64::from(col) + delta_col) % u64::from(self.width)) as u32;
+            (self.cells[self.get_index(r, c)] == Cell::Alive) == (expected != 0)
+        })
+    }
+
+    /// Tick until a pattern appears anywhere in the universe, for at most
+    /// `max_generations` ticks.
+    ///
+    /// Returns the generation at which the pattern first matched, counting
+    /// the current one as 0, or `None`. See `matches_pattern_at` for the
+    /// pattern layout. The universe is restored afterwards.
+    pub fn evolve_until_pattern(
+        &mut self,
+        pattern_width: u32,
+        pattern: &[u8],
+        max_generations: u32,
+    ) -> Option<u32> {
+        let saved = self.clone();
+        let mut found = None;
+        for generation in 0..=max_generations {
+            if generation > 0 {
+                self.tick();
+            }
+            let matched = (0..self.height).any(|row| {
+                (0..self.width).any(|col| self.matches_pattern_at(row, col, pattern_width, pattern))
+            });
+            if matched {
+                found = Some(generation);
+                break;
+            }
+        }
+        *self = saved;
+        found
+    }
+
+    /// Encode the cells as binary run lengths.
+    ///
+    /// The width and the height come first, as little-endian `u32`s. Then
+    /// each byte is the length of a run of cells, alternating dead and
+    /// alive and starting with dead, so `[2, 1, 3]` is two dead cells, one
+    /// live cell and three dead cells. A run longer than 255 cells is split
+    /// by a zero-length run of the other state, e.g. 300 dead cells are
+    /// `[255, 0, 45]`.
+    pub fn to_rle_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        push_u32(&mut bytes, self.width);
+        push_u32(&mut bytes, self.height);
+        let mut state = Cell::Dead;
+        let mut run: u32 = 0;
+        for &cell in &self.cells {
+            if cell != state {
+                push_run(&mut bytes, run);
+                state = cell;
+                run = 0;
+            }
+            run += 1;
+        }
+        push_run(&mut bytes, run);
+        bytes
+    }
+
+    /// Decode cells encoded by `to_rle_bytes`, or get `None` if the bytes
+    /// are malformed.
+    pub fn from_rle_bytes(bytes: &[u8]) -> Option<Universe> {
+        let mut reader = ByteReader::new(bytes);
+        let width = reader.u32()?;
+        let height = reader.u32()?;
+        let size = width.checked_mul(height)? as usize;
+        let runs = reader.bytes(bytes.len() - 8)?;
+        // Each run byte covers at most 255 cells, which bounds what to
+        // reserve whatever the header claims.
+        let mut cells = Vec::with_capacity(size.min(runs.len() * 255));
+        let mut state = Cell::Dead;
+        for &run in runs {
+            if cells.len() + run as usize > size {
+                return None;
+            }
+            cells.extend((0..run).map(|_| state));
+            state.toggle();
+        }
+        if cells.len() != size {
+            return None;
+        }
+
+        let mut universe = Universe::new();
+        universe.width = width;
+        universe.height = height;
+        universe.cells = cells;
+        Some(universe)
+    }
+
+    /// Save the current cells under `name`, replacing any earlier snapshot
+    /// with that name.
+    pub fn save_snapshot(&mut self, name: &str) {
+        self.snapshots.insert(
+            name.to_string(),
+            (self.width, self.height, self.cells.clone()),
+        );
+    }
+
+    /// Get the indices of the cells that differ from the snapshot saved as
+    /// `name`.
+    ///
+    /// Returns nothing if there is no such snapshot or its dimensions differ
+    /// from the universe's.
+    pub fn diff_snapshot(&self, name: &str) -> Vec<u32> {
+        match self.snapshots.get(name) {
+            Some(&(width, height, ref cells)) if width == self.width && height == self.height => {
+                self.diff_cells(cells)
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    pub fn boundary(&self) -> Boundary {
+        self.boundary
+    }
+
+    /// Set what lies past the edges of the universe.
+    pub fn set_boundary(&mut self, boundary: Boundary) {
+        self.boundary = boundary;
+    }
+
+    /// Make a rectangle of the universe wrap around on itself, as a small
+    /// torus inside the larger universe.
+    ///
+    /// The region is sealed at its seams: cells inside it only see cells
+    /// inside it, wrapping at the region's edges, and cells outside it see
+    /// the region as dead. Cells outside follow the universe's boundary as
+    /// usual. The rectangle is clipped to the universe; an empty one removes
+    /// the region.
+    pub fn set_wrap_region(&mut self, top: u32, left: u32, height: u32, width: u32) {
+        let height = height.min(self.height.saturating_sub(top));
+        let width = width.min(self.width.saturating_sub(left));
+        self.wrap_region = if height == 0 || width == 0 {
+            None
+        } else {
+            Some((top, left, height, width))
+        };
+    }
+
+    pub fn clear_wrap_region(&mut self) {
+        self.wrap_region = None;
+    }
+
+    /// Get the hash Golly's `g.hash()` computes for the bounding box of the
+    /// live cells.
+    ///
+    /// Golly returns the same 32 bits as a signed integer, so negative Golly
+    /// hashes show up here as values of 2³¹ and above. An empty universe
+    /// hashes to Golly's seed value, 31415962.
+    pub fn golly_hash(&self) -> u32 {
+        let mut hash: i32 = 31_415_962;
+        if let Some((min_row, min_col, _, _)) = self.bounding_box_of(&self.cells) {
+            for (row, col) in self.live_coordinates() {
+                hash = hash.wrapping_mul(1_000_003) ^ (row - min_row) as i32;
+                hash = hash.wrapping_mul(1_000_003) ^ (col - min_col) as i32;
+            }
+        }
+        hash as u32
+    }
+
+    /// Grow the universe after each tick, adding `margin` rows or columns
+    /// to each edge that live cells have reached, so a traveling pattern
+    /// never runs into it.
+    ///
+    /// Only the edges that are reached grow, keeping the universe a snug
+    /// rectangle around the pattern. At most `max_growth` rows and columns
+    /// are added in total. A `margin` of 0 turns growing off.
+    pub fn set_directional_growth(&mut self, margin: u32, max_growth: u32) {
+        self.growth_margin = margin;
+        self.growth_remaining = max_growth;
+    }
+
+    /// Compute the next generation into a second buffer, without advancing
+    /// the universe. Read it through `next_cells_ptr`.
+    pub fn precompute_next(&mut self) {
+        self.next = self.next_generation();
+    }
+
+    /// Get a pointer to the next generation computed by `precompute_next`,
+    /// laid out like `cells`.
+    ///
+    /// The buffer is only valid until the next call to `precompute_next`,
+    /// and is empty before the first one.
+    pub fn next_cells_ptr(&self) -> *const Cell {
+        self.next.as_ptr()
+    }
+
+    /// Create a universe from a grayscale image, one byte per pixel row by
+    /// row, with a live cell wherever the pixel is at least `threshold`.
+    pub fn from_grayscale(
+        width: u32,
+        height: u32,
+        pixels: &[u8],
+        threshold: u8,
+    ) -> Result<Universe, JsValue> {
+        if pixels.len() != width as usize * height as usize {
+            return Err(JsValue::from_str(&format!(
+                "expected {} pixels for a {}x{} image, got {}",
+                width as usize * height as usize,
+                width,
+                height,
+                pixels.len()
+            )));
+        }
+        let mut universe = Universe::new();
+        universe.width = width;
+        universe.height = height;
+        universe.cells = pixels
+            .iter()
+            .map(|&p| {
+                if p >= threshold {
+                    Cell::Alive
+                } else {
+                    Cell::Dead
+                }
+            })
+            .collect();
+        Ok(universe)
+    }
+
+    /// Get the sum of the squared distances of the live cells from their
+    /// center of mass, or 0 when at most one cell is alive.
+    pub fn moment_of_inertia(&self) -> f64 {
+        let live = self.live_coordinates();
+        if live.len() <= 1 {
+            return 0.0;
+        }
+        let n = live.len() as f64;
+        let center_row = live.iter().map(|&(row, _)| row as f64).sum::<f64>() / n;
+        let center_col = live.iter().map(|&(_, col)| col as f64).sum::<f64>() / n;
+        live.iter()
+            .map(|&(row, col)| {
+                let (dr, dc) = (row as f64 - center_row, col as f64 - center_col);
+                dr * dr + dc * dc
+            })
+            .sum()
+    }
+
+    /// Get the number of live cells.
+    pub fn count_alive(&self) -> u32 {
+        self.cells
+            .iter()
+            .filter(|&&cell| cell == Cell::Alive)
+            .count() as u32
+    }
+
+    /// Run `trials` random soups and summarize how they end, as JSON.
+    ///
+    /// Each soup is a fresh `size`×`size` universe with only this one's
+    /// rules, boundary and update mode, where each cell is alive with
+    /// probability `density`. It runs until it repeats an earlier state, for
+    /// at most `max_generations` ticks. The summary gives the mean, min and
+    /// max of the final population and of the lifespan, the generation at
+    /// which the soup became periodic (or `max_generations`):
+    ///
+    /// ```json
+    /// {"trials":2,"population":{"mean":4,"min":0,"max":8},"lifespan":{...}}
+    /// ```
+    ///
+    /// Soups are drawn from a generator seeded with `seed`. This universe is
+    /// left untouched.
+    pub fn batch_soup_stats(
+        &mut self,
+        trials: u32,
+        size: u32,
+        seed: u64,
+        density: f64,
+        max_generations: u32,
+    ) -> String {
+        let mut rng = Rng::new(seed);
+        let mut populations = Vec::with_capacity(trials as usize);
+        let mut lifespans = Vec::with_capacity(trials as usize);
+        for _ in 0..trials {
+            let mut soup = self.blank_with_settings(size, size);
+            soup.fill_random(&mut rng, density);
+            let lifespan = match soup.detect_cycle(max_generations) {
+                Some((start, _)) => start,
+                None => max_generations,
+            };
+            populations.push(soup.count_alive());
+            lifespans.push(lifespan);
+        }
+
+        fn summary(values: &[u32]) -> String {
+            let mean = if values.is_empty() {
+                0.0
+            } else {
+                values.iter().map(|&v| v as f64).sum::<f64>() / values.len() as f64
+            };
+            format!(
+                "{{\"mean\":{},\"min\":{},\"max\":{}}}",
+                mean,
+                values.iter().min().cloned().unwrap_or(0),
+                values.iter().max().cloned().unwrap_or(0)
+            )
+        }
+        format!(
+            "{{\"trials\":{},\"population\":{},\"lifespan\":{}}}",
+            trials,
+            summary(&populations),
+            summary(&lifespans)
+        )
+    }
+
+    /// Check whether, after `period` ticks, the universe equals its current
+    /// state moved by `(drow, dcol)`.
+    ///
+    /// Cells moved past an edge wrap around under `Boundary::Toroidal` and
+    /// are lost under `Boundary::Dead`. The universe is restored afterwards.
+    pub fn verify_spaceship(&mut self, period: u32, drow: i32, dcol: i32) -> bool {
+        let saved = self.clone();
+        let mut moved = vec![Cell::Dead; self.cells.len()];
+        for row in 0..self.height {
+            for col in 0..self.width {
+                if let Some((r, c)) = self.offset(row, col, -i64::from(drow), -i64::from(dcol)) {
+                    moved[self.get_index(row, col)] = self.cells[self.get_index(r, c)];
+                }
+            }
+        }
+        for _ in 0..period {
+            self.tick();
+        }
+        let verified = self.cells == moved;
+        *self = saved;
+        verified
+    }
+
+    /// Render the universe as an SVG document, `cell_size` units per cell.
+    ///
+    /// `alive` and `dead` are CSS colors. The dead color fills a background
+    /// rectangle, and each live cell gets its own `<rect>`.
+    pub fn to_svg(&self, cell_size: u32, alive: &str, dead: &str) -> String {
+        let (width, height) = (self.width * cell_size, self.height * cell_size);
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" \
+             viewBox=\"0 0 {w} {h}\">\n<rect width=\"{w}\" height=\"{h}\" fill=\"{fill}\"/>\n",
+            w = width,
+            h = height,
+            fill = escape_attribute(dead)
+        );
+        let fill = escape_attribute(alive);
+        for (row, col) in self.live_coordinates() {
+            svg.push_str(&format!(
+                "<rect x=\"{}\" y=\"{}\" width=\"{s}\" height=\"{s}\" fill=\"{}\"/>\n",
+                col * cell_size,
+                row * cell_size,
+                fill,
+                s = cell_size
+            ));
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Open a pattern written in RLE, resizing the universe to fit it with
+    /// `margin` dead cells on each side.
+    ///
+    /// All other cells are dead, the generation goes back to 0, any recording
+    /// stops, and the play area, wrap region, pulsers, sync base, rule
+    /// schedule, flip counts and ages are cleared. If the RLE header names a
+    /// rule, the universe switches to it.
+    pub fn open_pattern(&mut self, rle: &str, margin: u32) -> Result<(), JsValue> {
+        let pattern = rle::parse(rle).map_err(|e| JsValue::from_str(&e))?;
+        let padded = |length: u32| margin.checked_mul(2).and_then(|m| length.checked_add(m));
+        let (width, height) = match (padded(pattern.width), padded(pattern.height)) {
+            (Some(width), Some(height)) if width.checked_mul(height).is_some() => (width, height),
+            _ => return Err(JsValue::from_str("pattern is too large to open")),
+        };
+        self.width = width;
+        self.height = height;
+        self.cells = vec![Cell::Dead; (width * height) as usize];
+        self.play_area = None;
+        self.wrap_region = None;
+        self.pulsers.clear();
+        self.sync_base.clear();
+        self.rule_schedule.clear();
+        self.previous.clear();
+        self.flip_count.clear();
+        self.ages.clear();
+        self.generation = 0;
+        self.fit_to_size();
+        let cells: Vec<(u32, u32)> = pattern
+            .cells
+            .iter()
+            .map(|&(row, col)| (row + margin, col + margin))
+            .collect();
+        self.set_cells(&cells);
+        if let Some(rule) = pattern.rule {
+            self.rules = [rule; 2];
+        }
+        Ok(())
+    }
+
+    /// Tick `n` times.
+    pub fn tick_n(&mut self, n: u32) {
+        for _ in 0..n {
+            self.tick();
+        }
+    }
+
+    /// Suggest how many ticks to run per animation frame with `tick_n`.
+    ///
+    /// The suggestion keeps the work per frame at roughly 100,000 cell
+    /// updates, so small universes run many ticks per frame and large ones
+    /// a single tick. It is always between 1 and 100.
+    pub fn suggest_chunk_size(&self) -> u32 {
+        const CELL_UPDATES_PER_FRAME: u32 = 100_000;
+        let area = (self.width * self.height).max(1);
+        (CELL_UPDATES_PER_FRAME / area).clamp(1, 100)
+    }
+
+    /// Get the number of heap bytes held by the universe's buffers: the
+    /// cells, the precomputed next generation, the previous generation, the
+    /// sync base, the flip counts, the cell ages, the recording and its
+    /// last frame, and the snapshots.
+    ///
+    /// This counts allocated capacity, not just the bytes in use. The
+    /// bookkeeping of the snapshot table itself is not included.
+    pub fn memory_bytes(&self) -> usize {
+        let cell = std::mem::size_of::<Cell>();
+        let snapshots: usize = self
+            .snapshots
+            .iter()
+            .map(|(name, snapshot)| name.capacity() + snapshot.2.capacity() * cell)
+            .sum();
+        self.cells.capacity() * cell
+            + self.next.capacity() * cell
+            + self.previous.capacity() * cell
+            + self.sync_base.capacity() * cell
+            + self.flip_count.capacity() * std::mem::size_of::<u32>()
+            + self.ages.capacity() * std::mem::size_of::<u16>()
+            + self.recording.capacity()
+            + self.recorded_frame.capacity() * cell
+            + snapshots
+    }
+
+    /// Randomize the cells within `radius` of a center cell, making each
+    /// alive with probability `density`.
+    ///
+    /// The disk wraps around the edges under `Boundary::Toroidal` and is cut
+    /// off by them under `Boundary::Dead`; either way each cell is randomized
+    /// at most once. The same seed always scatters the same cells.
+    pub fn explode(
+        &mut self,
+        center_row: u32,
+        center_col: u32,
+        radius: u32,
+        seed: u64,
+        density: f64,
+    ) {
+        let mut rng = Rng::new(seed);
+        let (first_row, last_row) = self.disk_span(center_row, radius, self.height);
+        let (first_col, last_col) = self.disk_span(center_col, radius, self.width);
+        for delta_row in first_row..=last_row {
+            for delta_col in first_col..=last_col {
+                if i128::from(delta_row).pow(2) + i128::from(delta_col).pow(2)
+                    > i128::from(radius).pow(2)
+                {
+                    continue;
+                }
+                if let Some((row, col)) = self.offset(center_row, center_col, delta_row, delta_col)
+                {
+                    let idx = self.get_index(row, col);
+                    self.cells[idx] = if rng.next_f64() < density {
+                        Cell::Alive
+                    } else {
+                        Cell::Dead
+                    };
+                    self.forget_age(idx);
+                }
+            }
+        }
+    }
+
+    /// Measure how differently two rules behave, from 0 for identical
+    /// behavior up to 1.
+    ///
+    /// Each of the `trials` runs fills a fresh universe of this size and
+    /// boundary with a random soup (each cell alive with probability 0.5,
+    /// drawn from a generator seeded with `seed`), evolves a copy under each
+    /// rule for `generations` ticks and takes the fraction of cells that
+    /// disagree. Returns the mean over all trials, or an error if either rule
+    /// is invalid. This universe is left untouched.
+    pub fn rule_distance(
+        &mut self,
+        rule_a: &str,
+        rule_b: &str,
+        trials: u32,
+        generations: u32,
+        seed: u64,
+    ) -> Result<f64, JsValue> {
+        let rule_a = Rule::parse(rule_a).map_err(|e| JsValue::from_str(&e))?;
+        let rule_b = Rule::parse(rule_b).map_err(|e| JsValue::from_str(&e))?;
+        if trials == 0 || self.cells.is_empty() {
+            return Ok(0.0);
+        }
+        let mut rng = Rng::new(seed);
+        let mut soup = self.blank_with_settings(self.width, self.height);
+        let mut total = 0.0;
+        for _ in 0..trials {
+            soup.fill_random(&mut rng, 0.5);
+            let mut a = soup.clone();
+            let mut b = soup.clone();
+            a.rules = [rule_a; 2];
+            b.rules = [rule_b; 2];
+            a.tick_n(generations);
+            b.tick_n(generations);
+            total += a.diff_cells(&b.cells).len() as f64 / self.cells.len() as f64;
+        }
+        Ok(total / trials as f64)
+    }
+
+    /// Get the horizontal runs of live cells as flat
+    /// `(row, start_col, length)` triples, row by row.
+    pub fn live_spans(&self) -> Vec<u32> {
+        let mut spans = Vec::new();
+        for (row, line) in self.cells.chunks(self.width as usize).enumerate() {
+            let mut start = None;
+            for col in 0..=line.len() {
+                match (start, line.get(col) == Some(&Cell::Alive)) {
+                    (None, true) => start = Some(col),
+                    (Some(s), false) => {
+                        spans.extend_from_slice(&[row as u32, s as u32, (col - s) as u32]);
+                        start = None;
+                    }
+                    _ => {}
+                }
+            }
+        }
+        spans
+    }
+
+    /// Get the rule in B/S notation, such as `B3/S23`.
+    ///
+    /// With a checkerboard of two different rules, both are given, separated
+    /// by a comma.
+    pub fn rule_string(&self) -> String {
+        if self.rules[0] == self.rules[1] {
+            self.rules[0].to_string()
+        } else {
+            format!("{},{}", self.rules[0], self.rules[1])
+        }
+    }
+
+    /// Store a rule in one of four preset slots, numbered from 0, for
+    /// `apply_rule_preset`.
+    ///
+    /// Returns false, storing nothing, if the slot doesn't exist or the rule
+    /// is invalid.
+    pub fn store_rule_preset(&mut self, slot: u8, rulestring: &str) -> bool {
+        match (
+            self.rule_presets.get_mut(slot as usize),
+            Rule::parse(rulestring),
+        ) {
+            (Some(preset), Ok(rule)) => {
+                *preset = Some(rule);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Switch to the rule stored in a preset slot.
+    ///
+    /// Returns false, leaving the rule unchanged, if the slot is empty or
+    /// doesn't exist. Otherwise any rule schedule is dropped, so that it
+    /// doesn't switch back.
+    pub fn apply_rule_preset(&mut self, slot: u8) -> bool {
+        match self.rule_presets.get(slot as usize) {
+            Some(&Some(rule)) => {
+                self.rules = [rule; 2];
+                self.rule_schedule.clear();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Find how many ticks it takes the whole universe to return to its
+    /// current state, trying up to `max_period` ticks.
+    ///
+    /// This is the least common multiple of the periods of its parts, and 0
+    /// if it doesn't return within `max_period`. The universe is restored
+    /// afterward.
+    pub fn board_period(&mut self, max_period: u32) -> u32 {
+        let saved = self.clone();
+        let mut period = 0;
+        for generation in 1..=max_period {
+            self.tick();
+            if self.cells == saved.cells {
+                period = generation;
+                break;
+            }
+        }
+        *self = saved;
+        period
+    }
+
+    /// Set the cells along a line from `(r0, c0)` to `(r1, c1)`, using
+    /// Bresenham's algorithm. Cells off the universe are skipped.
+    pub fn draw_line(&mut self, r0: u32, c0: u32, r1: u32, c1: u32, alive: bool) {
+        let (rows, cols) = (
+            (i64::from(r0), i64::from(r1)),
+            (i64::from(c0), i64::from(c1)),
+        );
+        // Step along the longer axis, over only the part of it on the
+        // universe, working out each cell on the other axis directly rather
+        // than carrying Bresenham's error term from the start of the line.
+        let steep = (rows.1 - rows.0).abs() > (cols.1 - cols.0).abs();
+        let (major, minor, size) = if steep {
+            (rows, cols, i64::from(self.height))
+        } else {
+            (cols, rows, i64::from(self.width))
+        };
+        let (major_len, minor_len) = ((major.1 - major.0).abs(), (minor.1 - minor.0).abs());
+        let major_step = if major.0 < major.1 { 1 } else { -1 };
+        let minor_step = if minor.0 < minor.1 { 1 } else { -1 };
+        let (first, last) = if major_step == 1 {
+            (0, major_len.min(size - 1 - major.0))
+        } else {
+            ((major.0 - size + 1).max(0), major_len.min(major.0))
+        };
+        for step in first..=last {
+            // Round half up, as Bresenham's algorithm does.
+            let offset = if major_len == 0 {
+                0
+            } else {
+                let (step, major_len, minor_len) =
+                    (step as u128, major_len as u128, minor_len as u128);
+                ((2 * step * minor_len + major_len) / (2 * major_len)) as i64
+            };
+            let along = major.0 + major_step * step;
+            let across = minor.0 + minor_step * offset;
+            let (row, col) = if steep {
+                (along, across)
+            } else {
+                (across, along)
+            };
+            self.set_cell_clipped(row, col, alive);
+        }
+    }
+
+    /// Set the cells of a rectangle, or only those on its border if `filled`
+    /// is false. Cells off the universe are skipped.
+    pub fn draw_rect(
+        &mut self,
+        top: u32,
+        left: u32,
+        height: u32,
+        width: u32,
+        alive: bool,
+        filled: bool,
+    ) {
+        let (top, left) = (i64::from(top), i64::from(left));
+        let (bottom, right) = (top + i64::from(height) - 1, left + i64::from(width) - 1);
+        let last_row = bottom.min(i64::from(self.height) - 1);
+        let last_col = right.min(i64::from(self.width) - 1);
+        for row in top..=last_row {
+            for col in left..=last_col {
+                if filled || row == top || row == bottom || col == left || col == right {
+                    self.set_cell_clipped(row, col, alive);
+                }
+            }
+        }
+    }
+
+    /// Set the cells of a circle, or only those on its outline if `filled`
+    /// is false. Cells off the universe are skipped.
+    ///
+    /// The outline is drawn with the midpoint circle algorithm; a filled
+    /// circle is every cell within `radius` of the center.
+    pub fn draw_circle(
+        &mut self,
+        center_row: u32,
+        center_col: u32,
+        radius: u32,
+        alive: bool,
+        filled: bool,
+    ) {
+        let (center_row, center_col) = (i64::from(center_row), i64::from(center_col));
+        let radius = i64::from(radius);
+        // Only visit the part of the circle's bounding square on the universe.
+        let rows =
+            (center_row - radius).max(0)..=(center_row + radius).min(i64::from(self.height) - 1);
+        let cols =
+            (center_col - radius).max(0)..=(center_col + radius).min(i64::from(self.width) - 1);
+        for row in rows {
+            for col in cols.clone() {
+                let (delta_row, delta_col) = ((row - center_row).abs(), (col - center_col).abs());
+                let inside = if filled {
+                    i128::from(delta_row).pow(2) + i128::from(delta_col).pow(2)
+                        <= i128::from(radius).pow(2)
+                } else {
+                    let (near, far) = (delta_row.min(delta_col), delta_row.max(delta_col));
+                    midpoint_circle_x(radius, near) == Some(far)
+                };
+                if inside {
+                    self.set_cell_clipped(row, col, alive);
+                }
+            }
+        }
+    }
+
+    /// Set the region of same-state cells around `(row, col)`, connected
+    /// through edges but not corners, to alive or dead.
+    ///
+    /// The fill stops at the edges of the universe, even under
+    /// `Boundary::Toroidal`. Does nothing if the cell is already in the
+    /// target state.
+    pub fn flood_fill(&mut self, row: u32, col: u32, alive: bool) {
+        let target = if alive { Cell::Alive } else { Cell::Dead };
+        let start = self.get_index(row, col);
+        let source = self.cells[start];
+        if source == target {
+            return;
+        }
+        self.cells[start] = target;
+        self.forget_age(start);
+        let mut stack = vec![(row, col)];
+        while let Some((row, col)) = stack.pop() {
+            let neighbors = [
+                (row.wrapping_sub(1), col),
+                (row + 1, col),
+                (row, col.wrapping_sub(1)),
+                (row, col + 1),
+            ];
+            for &(row, col) in neighbors.iter() {
+                if row >= self.height || col >= self.width {
+                    continue;
+                }
+                let idx = self.get_index(row, col);
+                if self.cells[idx] == source {
+                    self.cells[idx] = target;
+                    self.forget_age(idx);
+                    stack.push((row, col));
+                }
+            }
+        }
+    }
+
+    /// Get a URL-safe string, starting with `#`, that holds the universe, its
+    /// rule and its boundary, for sharing with `from_url_fragment`.
+    ///
+    /// It has the form `#<rule>;<t|d>;<state>`, where the rule is as given
+    /// by `rule_string`, `t` or `d` is the boundary (toroidal or dead), and
+    /// the state is `to_bytes` in URL-safe base64.
+    pub fn to_url_fragment(&self) -> String {
+        let boundary = match self.boundary {
+            Boundary::Toroidal => 't',
+            Boundary::Dead => 'd',
+        };
+        format!(
+            "#{};{};{}",
+            self.rule_string(),
+            boundary,
+            base64::encode(&self.to_bytes())
+        )
+    }
+
+    /// Rebuild a universe from a string written by `to_url_fragment`. The
+    /// leading `#` is optional.
+    pub fn from_url_fragment(fragment: &str) -> Result<Universe, JsValue> {
+        Universe::parse_url_fragment(fragment).map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Flip up to `cells_per_step` of the cells that differ from `target`,
+    /// lowest index first, so that repeated calls morph this universe into
+    /// it.
+    ///
+    /// Returns whether the cells now equal the target's. Universes of
+    /// different sizes are never equal, and are left unchanged.
+    pub fn morph_step(&mut self, target: &Universe, cells_per_step: u32) -> bool {
+        if (self.width, self.height) != (target.width, target.height) {
+            return false;
+        }
+        let mut budget = cells_per_step;
+        for (idx, &wanted) in target.cells.iter().enumerate() {
+            if self.cells[idx] != wanted {
+                if budget == 0 {
+                    return false;
+                }
+                self.cells[idx] = wanted;
+                self.forget_age(idx);
+                budget -= 1;
+            }
+        }
+        true
+    }
+
+    /// Get the number of live cells in each row, from the top.
+    pub fn row_profile(&self) -> Vec<u32> {
+        self.cells
+            .chunks(self.width as usize)
+            .map(|row| row.iter().filter(|&&cell| cell == Cell::Alive).count() as u32)
+            .collect()
+    }
+
+    /// Get the number of live cells in each column, from the left.
+    pub fn col_profile(&self) -> Vec<u32> {
+        let mut profile = vec![0; self.width as usize];
+        for row in self.cells.chunks(self.width as usize) {
+            for (count, &cell) in profile.iter_mut().zip(row.iter()) {
+                *count += cell as u32;
+            }
+        }
+        profile
+    }
+
+    /// Tick `generations` times, then find the smallest period with which the
+    /// population repeats over those generations and the starting one.
+    ///
+    /// The population repeats at least as often as the cells do: a blinker
+    /// always has 3 live cells, so its population period is 1. Periods must
+    /// be seen at least twice to count; returns 0 if none is. The universe is
+    /// restored afterward.
+    pub fn population_period(&mut self, generations: u32) -> u32 {
+        let saved = self.clone();
+        let mut populations = vec![self.count_alive()];
+        for _ in 0..generations {
+            self.tick();
+            populations.push(self.count_alive());
+        }
+        *self = saved;
+        (1..=populations.len() / 2)
+            .find(|&period| {
+                populations
+                    .iter()
+                    .zip(&populations[period..])
+                    .all(|(a, b)| a == b)
+            })
+            .unwrap_or(0) as u32
+    }
+
+    /// Get how many times the universe has ticked.
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+
+    /// Force a cell alive after each tick to a generation that is `phase`
+    /// modulo `period`, whatever the rule says. It evolves normally on other
+    /// generations.
+    ///
+    /// Replaces any pulser already on the cell; a `period` of 0 removes it.
+    /// Cells off the universe are ignored, and pulsers left off it when the
+    /// universe shrinks are removed.
+    pub fn set_pulser(&mut self, row: u32, col: u32, period: u32, phase: u32) {
+        self.pulsers.retain(|&(r, c, _, _)| (r, c) != (row, col));
+        if period > 0 && row < self.height && col < self.width {
+            self.pulsers.push((row, col, period, phase % period));
+        }
+    }
+
+    /// Estimate the box-counting dimension of the live cells.
+    ///
+    /// The live bounding box is covered with square boxes of side 1, 2, 4
+    /// and so on up to the bounding box's longer side, counting the boxes
+    /// that hold a live cell. The estimate is the least-squares slope of the
+    /// log of the count against the log of the inverse side. Returns 0 when
+    /// there are fewer than two live cells to measure.
+    pub fn box_dimension(&self) -> f64 {
+        let (min_row, min_col, max_row, max_col) = match self.bounding_box_of(&self.cells) {
+            Some(bounds) => bounds,
+            None => return 0.0,
+        };
+        let extent = (max_row - min_row).max(max_col - min_col) + 1;
+        let live: Vec<(u32, u32)> = self
+            .live_coordinates()
+            .into_iter()
+            .map(|(row, col)| (row - min_row, col - min_col))
+            .collect();
+        let mut points = Vec::new();
+        let mut side = 1;
+        while side <= extent {
+            let boxes: HashSet<(u32, u32)> = live
+                .iter()
+                .map(|&(row, col)| (row / side, col / side))
+                .collect();
+            points.push((-f64::from(side).ln(), (boxes.len() as f64).ln()));
+            side *= 2;
+        }
+        if points.len() < 2 {
+            return 0.0;
+        }
+        let n = points.len() as f64;
+        let mean_x = points.iter().map(|p| p.0).sum::<f64>() / n;
+        let mean_y = points.iter().map(|p| p.1).sum::<f64>() / n;
+        let covariance: f64 = points.iter().map(|p| (p.0 - mean_x) * (p.1 - mean_y)).sum();
+        let variance: f64 = points.iter().map(|p| (p.0 - mean_x).powi(2)).sum();
+        covariance / variance
+    }
+
+    /// Apply the rule to a cell in state `current` with `live_neighbors` live
+    /// neighbors, without touching the universe.
+    ///
+    /// With a checkerboard of two rules, the rule for even squares is used.
+    pub fn next_state(&self, current: Cell, live_neighbors: u8) -> Cell {
+        self.rules[0].next(current, live_neighbors)
+    }
+
+    /// Get the whole universe, as written by `to_bytes`, and start tracking
+    /// changes from here for `delta_since_last_sync`.
+    pub fn full_sync(&mut self) -> Vec<u8> {
+        self.sync_base = self.cells.clone();
+        self.to_bytes()
+    }
+
+    /// Get the cells that changed since the last sync, then start tracking
+    /// changes from here.
+    ///
+    /// The delta is the number of changed cells, then their indices, as
+    /// little-endian `u32`s; apply it to a copy with `apply_delta`. A cell
+    /// that changed and changed back over several ticks isn't included.
+    /// Returns no bytes at all if there has been no `full_sync` since the
+    /// universe was last resized, in which case a full sync is needed.
+    pub fn delta_since_last_sync(&mut self) -> Vec<u8> {
+        if self.sync_base.len() != self.cells.len() {
+            return Vec::new();
+        }
+        let changed = self.diff_cells(&self.sync_base);
+        let mut bytes = Vec::with_capacity(4 * (changed.len() + 1));
+        push_u32(&mut bytes, changed.len() as u32);
+        for idx in changed {
+            push_u32(&mut bytes, idx);
+        }
+        self.sync_base.copy_from_slice(&self.cells);
+        bytes
+    }
+
+    /// Flip the cells listed in a delta from `delta_since_last_sync`.
+    ///
+    /// Returns false, leaving the universe unchanged, if the delta is
+    /// malformed or names a cell outside the universe.
+    pub fn apply_delta(&mut self, delta: &[u8]) -> bool {
+        let mut reader = ByteReader::new(delta);
+        let mut changed = Vec::new();
+        if let Some(count) = reader.u32() {
+            for _ in 0..count {
+                match reader.u32() {
+                    Some(idx) if (idx as usize) < self.cells.len() => changed.push(idx),
+                    _ => return false,
+                }
+            }
+        }
+        if delta.is_empty() || !reader.is_empty() {
+            return false;
+        }
+        for idx in changed {
+            self.cells[idx as usize].toggle();
+            self.forget_age(idx as usize);
+        }
+        true
+    }
+
+    /// Check whether any of the next `generations` ticks would come out
+    /// differently if everything past the edges were dead, meaning the
+    /// pattern actually relies on the edges wrapping around.
+    ///
+    /// Always false under `Boundary::Dead`. The universe is restored
+    /// afterward.
+    pub fn uses_wrap(&mut self, generations: u32) -> bool {
+        if self.boundary == Boundary::Dead {
+            return false;
+        }
+        let saved = self.clone();
+        let mut wrapped = false;
+        for _ in 0..generations {
+            let toroidal = self.next_generation();
+            self.boundary = Boundary::Dead;
+            let dead = self.next_generation();
+            self.boundary = Boundary::Toroidal;
+            if toroidal != dead {
+                wrapped = true;
+                break;
+            }
+            self.tick();
+        }
+        *self = saved;
+        wrapped
+    }
+
+    /// Get the indices of the cells, alive or dead, that keep their state
+    /// through each of the next `generations` ticks.
+    ///
+    /// The universe is restored afterward.
+    pub fn stable_cells(&mut self, generations: u32) -> Vec<u32> {
+        let saved = self.clone();
+        let mut stable = vec![true; self.cells.len()];
+        for _ in 0..generations {
+            self.tick();
+            if self.cells.len() != saved.cells.len() {
+                break;
+            }
+            for (is_stable, (&now, &start)) in stable
+                .iter_mut()
+                .zip(self.cells.iter().zip(saved.cells.iter()))
+            {
+                *is_stable &= now == start;
+            }
+        }
+        *self = saved;
+        (0..stable.len() as u32)
+            .filter(|&idx| stable[idx as usize])
+            .collect()
+    }
+
+    /// Get the symmetries of the live cells within their bounding box, as
+    /// `Symmetry` flags combined with `|`.
+    ///
+    /// Quarter turns and diagonal mirrors need a square bounding box. With no
+    /// live cells, every symmetry holds.
+    pub fn symmetry_class(&self) -> u8 {
+        let (min_row, min_col, max_row, max_col) = match self.bounding_box_of(&self.cells) {
+            Some(bounds) => bounds,
+            None => return 0b11_1111,
+        };
+        let (last_row, last_col) = (max_row - min_row, max_col - min_col);
+        let live: HashSet<(u32, u32)> = self
+            .live_coordinates()
+            .into_iter()
+            .map(|(row, col)| (row - min_row, col - min_col))
+            .collect();
+        let holds = |map: &dyn Fn(u32, u32) -> (u32, u32)| {
+            live.iter().all(|&(row, col)| live.contains(&map(row, col)))
+        };
+        let square = last_row == last_col;
+        let mut class = 0;
+        if holds(&|row, col| (last_row - row, col)) {
+            class |= Symmetry::Horizontal as u8;
+        }
+        if holds(&|row, col| (row, last_col - col)) {
+            class |= Symmetry::Vertical as u8;
+        }
+        if holds(&|row, col| (last_row - row, last_col - col)) {
+            class |= Symmetry::Rotate180 as u8;
+        }
+        if square && holds(&|row, col| (col, last_row - row)) {
+            class |= Symmetry::Rotate90 as u8;
+        }
+        if square && holds(&|row, col| (col, row)) {
+            class |= Symmetry::Diagonal as u8;
+        }
+        if square && holds(&|row, col| (last_col - col, last_row - row)) {
+            class |= Symmetry::AntiDiagonal as u8;
+        }
+        class
+    }
+
+    /// Tick until the live cells have the `target` symmetry, for at most
+    /// `max_generations` ticks.
+    ///
+    /// Returns the number of ticks, which is 0 if the symmetry already holds,
+    /// or `None` if it never did. The universe is left in its final state.
+    pub fn evolve_until_symmetry(&mut self, target: Symmetry, max_generations: u32) -> Option<u32> {
+        for generation in 0..=max_generations {
+            if generation > 0 {
+                self.tick();
+            }
+            if self.symmetry_class() & target as u8 != 0 {
+                return Some(generation);
+            }
+        }
+        None
+    }
+
+    /// Count the distinct states the universe passes through, starting with
+    /// the current one, before it repeats one, for at most `max_generations`
+    /// ticks.
+    ///
+    /// This is the length of the transient plus the period. If no state
+    /// repeats in time, it is the `max_generations + 1` states seen. The
+    /// universe is restored afterward.
+    pub fn trajectory_length(&mut self, max_generations: u32) -> u32 {
+        let saved = self.clone();
+        let length = match self.detect_cycle(max_generations) {
+            Some((start, period)) => start + period,
+            None => max_generations + 1,
+        };
+        *self = saved;
+        length
+    }
+
+    /// Render a smooth RGBA thumbnail of the universe, `out_width` by
+    /// `out_height` pixels.
+    ///
+    /// Each pixel blends the `alive` and `dead` colors, channel by channel,
+    /// in proportion to the live-cell density of its block of cells, as in
+    /// `minimap`. Colors are packed as `0xRRGGBBAA`.
+    pub fn render_thumbnail_rgba(
+        &self,
+        out_width: u32,
+        out_height: u32,
+        alive: u32,
+        dead: u32,
+    ) -> Vec<u8> {
+        let (alive, dead) = (alive.to_be_bytes(), dead.to_be_bytes());
+        let mut pixels = Vec::with_capacity((out_width * out_height * 4) as usize);
+        for density in self.minimap(out_width, out_height) {
+            let density = u32::from(density);
+            for (&a, &d) in alive.iter().zip(dead.iter()) {
+                let blended = (u32::from(a) * density + u32::from(d) * (255 - density) + 127) / 255;
+                pixels.push(blended as u8);
+            }
+        }
+        pixels
+    }
+
+    /// Render the universe like `render_rgba`, with the outermost ring of
+    /// pixels drawn in the `seam` color to show where the edges wrap around.
+    ///
+    /// Under `Boundary::Dead`, where the edges don't wrap, the ring uses the
+    /// `seam` color at half its alpha instead, so the two modes can be told
+    /// apart.
+    pub fn render_rgba_seam(&self, cell_size: u32, alive: u32, dead: u32, seam: u32) -> Vec<u8> {
+        let mut pixels = self.render_rgba(cell_size, alive, dead);
+        let seam = match self.boundary {
+            Boundary::Toroidal => seam,
+            Boundary::Dead => (seam & !0xff) | ((seam & 0xff) / 2),
+        };
+        let image_width = (self.width * cell_size) as usize;
+        let image_height = (self.height * cell_size) as usize;
+        for (i, pixel) in pixels.chunks_mut(4).enumerate() {
+            let (y, x) = (i / image_width, i % image_width);
+            if y == 0 || x == 0 || y == image_height - 1 || x == image_width - 1 {
+                pixel.copy_from_slice(&seam.to_be_bytes());
+            }
+        }
+        pixels
+    }
+
+    /// Find how often the cell at `(detector_row, detector_col)` switches on
+    /// over the next `max_generations` ticks, as when gliders from a gun
+    /// pass through it.
+    ///
+    /// Returns the number of ticks between switch-ons if it is always the
+    /// same. When each passing glider switches the cell on more than once,
+    /// the gaps between switch-ons repeat in a cycle instead, and the cycle's
+    /// total length is returned; a cycle of several gaps must be seen at
+    /// least twice. Returns 0 if the cell switches on fewer than twice or
+    /// irregularly. The universe is restored afterward.
+    pub fn emission_period(
+        &mut self,
+        detector_row: u32,
+        detector_col: u32,
+        max_generations: u32,
+    ) -> u32 {
+        let saved = self.clone();
+        let mut switch_ons = Vec::new();
+        let mut was_alive = self.cells[self.get_index(detector_row, detector_col)] == Cell::Alive;
+        for generation in 1..=max_generations {
+            self.tick();
+            let alive = self.cells[self.get_index(detector_row, detector_col)] == Cell::Alive;
+            if alive && !was_alive {
+                switch_ons.push(generation);
+            }
+            was_alive = alive;
+        }
+        *self = saved;
+        let gaps: Vec<u32> = switch_ons
+            .windows(2)
+            .map(|pair| pair[1] - pair[0])
+            .collect();
+        (1..=gaps.len())
+            .find(|&cycle| {
+                (cycle == 1 || 2 * cycle <= gaps.len())
+                    && gaps.iter().zip(&gaps[cycle..]).all(|(a, b)| a == b)
+            })
+            .map_or(0, |cycle| gaps[..cycle].iter().sum())
+    }
+
+    /// Start or stop counting how often each cell changes, for
+    /// `activity_centroid`.
+    ///
+    /// Counting is off by default, as it adds a pass over the board to every
+    /// tick. Stopping forgets the counts.
+    pub fn set_track_flips(&mut self, track: bool) {
+        self.track_flips = track;
+        if !track {
+            self.flip_count = Vec::new();
+        }
+    }
+
+    /// Forget how often each cell has changed, so that `activity_centroid`
+    /// only follows changes from here on.
+    pub fn reset_flip_counts(&mut self) {
+        self.flip_count.clear();
+    }
+
+    /// Check whether one tick turns this universe into `target`.
+    ///
+    /// The two must also have the same size, boundary and rules.
+    pub fn is_predecessor_of(&self, target: &Universe) -> bool {
+        (self.width, self.height) == (target.width, target.height)
+            && self.boundary == target.boundary
+            && self.rules == target.rules
+            && self.peek_next() == target.cells
+    }
+
+    /// Pick up to `count` different live cells at random, as flat
+    /// `[row, col, row, col, ...]` pairs.
+    ///
+    /// The choice is drawn from a generator seeded with `seed`, so it is
+    /// reproducible. If `count` is at least the population, every live cell
+    /// is returned, in row-major order.
+    pub fn sample_live(&self, count: u32, seed: u64) -> Vec<u32> {
+        let mut live = self.live_coordinates();
+        if (count as usize) < live.len() {
+            let mut rng = Rng::new(seed);
+            for i in 0..count as usize {
+                let j = i + rng.below((live.len() - i) as u64) as usize;
+                live.swap(i, j);
+            }
+            live.truncate(count as usize);
+        }
+        live.into_iter()
+            .flat_map(|(row, col)| vec![row, col])
+            .collect()
+    }
+
+    /// Tick `generations` times, recording the number of separate groups of
+    /// live cells after each tick.
+    ///
+    /// Groups are as in `settle_species`: cells that touch, including
+    /// diagonally. The universe is restored afterward.
+    pub fn component_count_series(&mut self, generations: u32) -> Vec<u32> {
+        let saved = self.clone();
+        let mut series = Vec::with_capacity(generations as usize);
+        for _ in 0..generations {
+            self.tick();
+            series.push(self.components().len() as u32);
+        }
+        *self = saved;
+        series
+    }
+
+    /// Get the name of the global allocator this build uses: `wee_alloc`
+    /// with the `wee_alloc` feature, `system` otherwise.
+    pub fn allocator_name() -> String {
+        ALLOCATOR_NAME.to_string()
+    }
+
+    /// Tick `generations` times, calling `callback(generation, population)`
+    /// after each tick, where `generation` is as given by `generation`.
+    ///
+    /// Each call happens between whole ticks, and an exception thrown by the
+    /// callback is ignored, so the run always completes.
+    pub fn run_with_callback(&mut self, generations: u32, callback: js_sys::Function) {
+        self.run_with(generations, |generation, population| {
+            let _ = callback.call2(&JsValue::NULL, &generation.into(), &population.into());
+        });
+    }
+
+    /// Find how far, in cells, the pattern spreads past its current bounding
+    /// box over the next `generations` ticks, at most.
+    ///
+    /// The pattern is run alone on a board with dead edges far enough away
+    /// not to affect it, so a board with this much empty margin around the
+    /// pattern is safe for that many ticks. Spaceships count: a glider
+    /// spreads one cell every 4 ticks. The universe itself is not changed.
+    pub fn safe_margin(&self, generations: u32) -> u32 {
+        let points: Vec<(i64, i64)> = self
+            .live_coordinates()
+            .into_iter()
+            .map(|(row, col)| (i64::from(row), i64::from(col)))
+            .collect();
+        if points.is_empty() {
+            return 0;
+        }
+        let margin = generations + 1;
+        let mut copy = self.isolate(&points, margin);
+        let (top, left, bottom, right) = copy.bounding_box_of(&copy.cells).unwrap();
+        let mut spread = 0;
+        for _ in 0..generations {
+            copy.tick();
+            if let Some((min_row, min_col, max_row, max_col)) = copy.bounding_box_of(&copy.cells) {
+                spread = spread
+                    .max(top.saturating_sub(min_row))
+                    .max(left.saturating_sub(min_col))
+                    .max(max_row.saturating_sub(bottom))
+                    .max(max_col.saturating_sub(right));
+            }
+        }
+        spread
+    }
+
+    /// Draw the live cells' bounding box as text in the Life Lexicon style,
+    /// with `*` for live cells and `.` for dead ones, one line per row.
+    ///
+    /// Every line ends with a newline. An empty universe gives an empty
+    /// string.
+    pub fn to_lexicon_art(&self) -> String {
+        let (min_row, min_col, max_row, max_col) = match self.bounding_box_of(&self.cells) {
+            Some(bounds) => bounds,
+            None => return String::new(),
+        };
+        let mut art = String::new();
+        for row in min_row..=max_row {
+            for col in min_col..=max_col {
+                art.push(match self.cells[self.get_index(row, col)] {
+                    Cell::Alive => '*',
+                    Cell::Dead => '.',
+                });
+            }
+            art.push('\n');
+        }
+        art
+    }
+
+    /// Check whether some 3x3 block of cells within the universe could not
+    /// have come out of any tick under the current rules, so the universe
+    /// is a Garden of Eden that can only be set up by hand.
+    ///
+    /// This is sufficient but not necessary: most Gardens of Eden only show
+    /// over larger areas. Under `B3/S23` every 3x3 block has a predecessor,
+    /// so this is only ever true for other rules. The blocks with no
+    /// predecessor are worked out once for each set of rules and kept.
+    pub fn has_orphan_neighborhood(&mut self) -> bool {
+        if self.width < 3 || self.height < 3 {
+            return false;
+        }
+        let rules = self.rules;
+        if self.reachable_windows.as_ref().map(|&(cached, _)| cached) != Some(rules) {
+            let reachable = [
+                rule::reachable_windows(rules),
+                rule::reachable_windows([rules[1], rules[0]]),
+            ];
+            self.reachable_windows = Some((rules, reachable));
+        }
+        let reachable = &self.reachable_windows.as_ref().unwrap().1;
+        for top in 0..self.height - 2 {
+            for left in 0..self.width - 2 {
+                let mut window = 0;
+                for row in 0..3 {
+                    for col in 0..3 {
+                        let idx = self.get_index(top + row, left + col);
+                        window |= (self.cells[idx] as usize) << (3 * row + col);
+                    }
+                }
+                if !reachable[((top + left) % 2) as usize][window] {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    /// Get the length of `to_rle_bytes` over the length of the cells packed
+    /// one bit each, as a cheap measure of how orderly the universe is.
+    ///
+    /// Large empty or full areas give low ratios; noisy soups give ratios
+    /// well above 1. An empty universe gives 0.
+    pub fn compressibility(&self) -> f64 {
+        let packed = self.cells.len().div_ceil(8);
+        if packed == 0 {
+            return 0.0;
+        }
+        self.to_rle_bytes().len() as f64 / packed as f64
+    }
+
+    /// Get how many ticks the cell at `(row, col)` has survived since it
+    /// came alive, or 0 if it is dead.
+    ///
+    /// Cells brought to life by hand, or by a tick, start at 0. Ages start
+    /// over when the universe is resized, and are only counted while
+    /// `set_track_ages` is on.
+    pub fn age(&self, row: u32, col: u32) -> u16 {
+        let idx = self.get_index(row, col);
+        match (self.cells[idx], self.ages.get(idx)) {
+            (Cell::Alive, Some(&age)) => age,
+            _ => 0,
+        }
+    }
+
+    /// Start or stop counting how long each cell has been alive, for `age`
+    /// and the `kill_*_than` methods.
+    ///
+    /// Counting is off by default, as it adds a pass over the board to every
+    /// tick. Stopping forgets the ages.
+    pub fn set_track_ages(&mut self, track: bool) {
+        self.track_ages = track;
+        if !track {
+            self.ages = Vec::new();
+        }
+    }
+
+    /// Kill every live cell whose `age` is greater than `age`.
+    pub fn kill_older_than(&mut self, age: u16) {
+        self.kill_where_age(|cell_age| cell_age > age);
+    }
+
+    /// Kill every live cell whose `age` is less than `age`.
+    pub fn kill_younger_than(&mut self, age: u16) {
+        self.kill_where_age(|cell_age| cell_age < age);
+    }
+
+    /// Get the distance between each pair of separate groups of live cells,
+    /// measured between their nearest cells, center to center.
+    ///
+    /// Groups are as in `settle_species`, in the row-major order of their
+    /// first cells, and pairs are listed as `(0, 1), (0, 2), ..., (1, 2),
+    /// ...`. Under `Boundary::Toroidal`, distances go the short way around
+    /// the edges.
+    pub fn component_distances(&self) -> Vec<f32> {
+        let (height, width) = (i64::from(self.height), i64::from(self.width));
+        let groups: Vec<Vec<(i64, i64)>> = self
+            .components_with_starts()
+            .into_iter()
+            .map(|(start, component)| {
+                let (row, col) = (start / self.width, start % self.width);
+                component
+                    .into_iter()
+                    .map(|(rel_row, rel_col)| {
+                        (
+                            (i64::from(row) + rel_row).rem_euclid(height),
+                            (i64::from(col) + rel_col).rem_euclid(width),
+                        )
+                    })
+                    .collect()
+            })
+            .collect();
+        let toroidal = self.boundary == Boundary::Toroidal;
+        let axis = |a: i64, b: i64, size: i64| {
+            let d = (a - b).abs();
+            if toroidal {
+                d.min(size - d)
+            } else {
+                d
+            }
+        };
+        let mut distances = Vec::new();
+        for (i, a) in groups.iter().enumerate() {
+            for b in &groups[i + 1..] {
+                let mut nearest = i64::MAX;
+                for &(row_a, col_a) in a {
+                    for &(row_b, col_b) in b {
+                        let (dr, dc) = (axis(row_a, row_b, height), axis(col_a, col_b, width));
+                        nearest = nearest.min(dr * dr + dc * dc);
+                    }
+                }
+                distances.push((nearest as f32).sqrt());
+            }
+        }
+        distances
+    }
+
+    /// Let the live cells fall towards one edge, so that in each column (or
+    /// row, for left and right) they stack up against that edge with no gaps.
+    ///
+    /// This ignores the rule entirely.
+    pub fn apply_gravity(&mut self, direction: Direction) {
+        let (lines, length) = match direction {
+            Direction::Up | Direction::Down => (self.width, self.height),
+            Direction::Left | Direction::Right => (self.height, self.width),
+        };
+        for line in 0..lines {
+            let index = |i: u32| match direction {
+                Direction::Up | Direction::Down => self.get_index(i, line),
+                Direction::Left | Direction::Right => self.get_index(line, i),
+            };
+            let live = (0..length)
+                .filter(|&i| self.cells[index(i)] == Cell::Alive)
+                .count() as u32;
+            let stacked = match direction {
+                Direction::Up | Direction::Left => 0..live,
+                Direction::Down | Direction::Right => length - live..length,
+            };
+            let indices: Vec<usize> = (0..length).map(index).collect();
+            for (i, idx) in indices.into_iter().enumerate() {
+                self.cells[idx] = if stacked.contains(&(i as u32)) {
+                    Cell::Alive
+                } else {
+                    Cell::Dead
+                };
+                self.forget_age(idx);
+            }
+        }
+    }
+
+    /// Score how interesting the universe's future is, for ranking soups.
+    ///
+    /// The universe is settled as in `settle_species`, for at most
+    /// `max_generations` ticks. The score is the number of ticks that took,
+    /// plus 10 for each distinct species left, plus 50 if a glider is left
+    /// flying. The universe is restored afterward.
+    pub fn interest_score(&mut self, max_generations: u32) -> f64 {
+        let saved = self.clone();
+        let lifespan = self.settle(max_generations);
+        let species: HashSet<Vec<(i64, i64)>> = self
+            .components()
+            .iter()
+            .map(|component| pattern::canonical(component))
+            .collect();
+        let has_glider = !self.find_gliders().is_empty();
+        *self = saved;
+        f64::from(lifespan) + 10.0 * species.len() as f64 + if has_glider { 50.0 } else { 0.0 }
+    }
+
+    /// Tick `generations` times, recording the bounding box of the live
+    /// cells after each tick as flat `min_row, min_col, max_row, max_col`
+    /// quadruples.
+    ///
+    /// A frame with no live cells is recorded as four `u32::MAX`s. The
+    /// universe is restored afterward.
+    pub fn bounding_box_series(&mut self, generations: u32) -> Vec<u32> {
+        let saved = self.clone();
+        let mut series = Vec::with_capacity(4 * generations as usize);
+        for _ in 0..generations {
+            self.tick();
+            let (min_row, min_col, max_row, max_col) = self
+                .bounding_box_of(&self.cells)
+                .unwrap_or((u32::MAX, u32::MAX, u32::MAX, u32::MAX));
+            series.extend_from_slice(&[min_row, min_col, max_row, max_col]);
+        }
+        *self = saved;
+        series
+    }
+
+    /// Set every cell to the majority state of the 3x3 block around it,
+    /// itself included, so it comes alive with 5 or more live cells in the
+    /// block.
+    ///
+    /// Neighbors are found as `tick` finds them, so the boundary is
+    /// respected. This is a one-off filter, e.g. for cleaning up an imported
+    /// image, and ignores the rule.
+    pub fn smooth(&mut self) {
+        let mut smoothed = self.cells.clone();
+        for row in 0..self.height {
+            for col in 0..self.width {
+                let idx = self.get_index(row, col);
+                let live = self.live_neighbor_count(row, col) + self.cells[idx] as u8;
+                smoothed[idx] = if live >= 5 { Cell::Alive } else { Cell::Dead };
+                self.forget_age(idx);
+            }
+        }
+        self.cells = smoothed;
+    }
+
+    /// Find after how many ticks the population reaches `target`, or passes
+    /// it on the way up or down, trying up to `max_generations` ticks.
+    ///
+    /// Returns 0 if the population is already `target`, or `None` if it
+    /// doesn't get there in time. The universe is restored afterward.
+    pub fn generations_until_population(
+        &mut self,
+        target: u32,
+        max_generations: u32,
+    ) -> Option<u32> {
+        let saved = self.clone();
+        let mut population = self.count_alive();
+        if population == target {
+            return Some(0);
+        }
+        let mut found = None;
+        for generation in 1..=max_generations {
+            self.tick();
+            let previous = population;
+            population = self.count_alive();
+            if population == target || (previous < target) != (population < target) {
+                found = Some(generation);
+                break;
+            }
+        }
+        *self = saved;
+        found
+    }
+
+    /// Render the universe like `render_rgba`, with gridlines in the `line`
+    /// color every `spacing` cells for counting coordinates.
+    ///
+    /// A gridline is the first row or column of pixels of every cell whose
+    /// row or column is a multiple of `spacing`, starting from the top-left
+    /// edge. A `spacing` of 0 draws no gridlines.
+    pub fn render_with_gridlines(
+        &self,
+        cell_size: u32,
+        alive: u32,
+        dead: u32,
+        line: u32,
+        spacing: u32,
+    ) -> Vec<u8> {
+        let mut pixels = self.render_rgba(cell_size, alive, dead);
+        if spacing == 0 {
+            return pixels;
+        }
+        let on_line = |pixel: u32| {
+            pixel.is_multiple_of(cell_size) && (pixel / cell_size).is_multiple_of(spacing)
+        };
+        let image_width = self.width * cell_size;
+        for (i, pixel) in pixels.chunks_mut(4).enumerate() {
+            let (y, x) = (i as u32 / image_width, i as u32 % image_width);
+            if on_line(x) || on_line(y) {
+                pixel.copy_from_slice(&line.to_be_bytes());
+            }
+        }
+        pixels
+    }
+
+    /// Get a 64-bit FNV-1a hash of the universe's size and cells, so equal
+    /// universes hash equally and different ones almost never do.
+    pub fn state_hash(&self) -> u64 {
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        let size = [self.width.to_le_bytes(), self.height.to_le_bytes()];
+        let cells = self.cells.iter().map(|&cell| cell as u8);
+        for byte in size
+            .iter()
+            .flat_map(|bytes| bytes.iter().cloned())
+            .chain(cells)
+        {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+        hash
+    }
+
+    /// Get an opaque color, packed as `0xRRGGBBAA`, made from `state_hash`,
+    /// for telling saved states apart at a glance.
+    pub fn state_color(&self) -> u32 {
+        let hash = self.state_hash();
+        let folded = (hash ^ (hash >> 32)) as u32;
+        (folded << 8) | 0xff
+    }
+
+    /// Switch rules on a schedule, given as comma-separated
+    /// `generation:rule` entries such as `0:B3/S23,100:B36/S23`.
+    ///
+    /// Each rule takes over once the universe reaches its generation, as
+    /// given by `generation`, and applies to the ticks from then on. The
+    /// latest entry already reached takes over right away, and `untick` goes
+    /// back to the rule that was in use. An empty schedule keeps the current
+    /// rule from here on, and an invalid one is ignored. Setting a rule by
+    /// hand drops the schedule.
+    pub fn set_rule_schedule(&mut self, schedule: &str) {
+        if let Ok(mut entries) = parse_rule_schedule(schedule) {
+            entries.sort_by_key(|&(generation, _)| generation);
+            if self.rule_schedule.is_empty() {
+                self.unscheduled_rules = self.rules;
+            }
+            self.rule_schedule = entries;
+            self.follow_rule_schedule();
+        }
+    }
+
+    /// Get a 64-bit FNV-1a hash of the live cells' shape, the same wherever
+    /// the pattern sits and however it is rotated or reflected.
+    pub fn canonical_hash(&self) -> u64 {
+        let points: Vec<(i64, i64)> = self
+            .live_coordinates()
+            .into_iter()
+            .map(|(row, col)| (i64::from(row), i64::from(col)))
+            .collect();
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        for (row, col) in pattern::canonical(&points) {
+            for byte in (row as u32)
+                .to_le_bytes()
+                .iter()
+                .chain((col as u32).to_le_bytes().iter())
+            {
+                hash ^= u64::from(*byte);
+                hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+            }
+        }
+        hash
+    }
+
+    /// Get a short id for the live cells' shape: `canonical_hash` written as
+    /// 13 characters of Crockford's base32, in lowercase.
+    pub fn pattern_id(&self) -> String {
+        const ALPHABET: &[u8; 32] = b"0123456789abcdefghjkmnpqrstvwxyz";
+        let hash = self.canonical_hash();
+        (0..13)
+            .rev()
+            .map(|i| ALPHABET[(hash >> (5 * i) & 31) as usize] as char)
+            .collect()
+    }
+
+    /// Apply one stage of a tick split into `stages` stages: stage `stage`
+    /// updates the cells whose index is `stage` modulo `stages`.
+    ///
+    /// Stage 0 computes the next generation, as `precompute_next` does, and
+    /// later stages reuse it, so running stages `0..stages` in order gives
+    /// the same cells as one synchronous `tick`. Only the cells change, and
+    /// their ages start over; the generation count and other per-tick
+    /// bookkeeping are left alone. Does nothing if `stage` is not less than
+    /// `stages`.
+    pub fn tick_staged(&mut self, stage: u32, stages: u32) {
+        if stage >= stages {
+            return;
+        }
+        if stage == 0 || self.next.len() != self.cells.len() {
+            self.precompute_next();
+        }
+        let stages = stages as usize;
+        for idx in (stage as usize..self.cells.len()).step_by(stages) {
+            self.cells[idx] = self.next[idx];
+            self.forget_age(idx);
+        }
+    }
+
+    /// Get the discrete Laplacian of the live cells: for each cell, its live
+    /// orthogonal neighbors minus four if it is alive itself.
+    ///
+    /// Values range from -4 to 4, negative along the inside of a pattern's
+    /// edges and positive along the outside. Neighbors follow the boundary.
+    pub fn laplacian(&self) -> Vec<i8> {
+        let mut values = Vec::with_capacity(self.cells.len());
+        for row in 0..self.height {
+            for col in 0..self.width {
+                let neighbors: i8 = [(-1, 0), (1, 0), (0, -1), (0, 1)]
+                    .iter()
+                    .filter_map(|&(delta_row, delta_col)| {
+                        self.offset(row, col, delta_row, delta_col)
+                    })
+                    .map(|(r, c)| self.cells[self.get_index(r, c)] as i8)
+                    .sum();
+                values.push(neighbors - 4 * self.cells[self.get_index(row, col)] as i8);
+            }
+        }
+        values
+    }
+
+    /// Switch to `UpdateMode::SecondOrder`, which `untick` and `step_by`
+    /// need, or back from it to `UpdateMode::Synchronous`.
+    ///
+    /// Switching off leaves any other update mode as it is.
+    pub fn set_second_order(&mut self, on: bool) {
+        if on {
+            self.set_update_mode(UpdateMode::SecondOrder);
+        } else if self.update_mode == UpdateMode::SecondOrder {
+            self.set_update_mode(UpdateMode::Synchronous);
+        }
+    }
+
+    /// Step back one generation under `UpdateMode::SecondOrder`, or fail in
+    /// any other update mode, where ticks can't be undone, or at generation 0.
+    ///
+    /// Only the cells and the generation counter go back; pulsers, flip
+    /// counts, ages and recordings are left as they are.
+    pub fn untick(&mut self) -> Result<(), JsValue> {
+        if self.update_mode != UpdateMode::SecondOrder {
+            return Err(JsValue::from_str(
+                "untick needs the second-order update mode",
+            ));
+        }
+        if self.generation == 0 {
+            return Err(JsValue::from_str("can't untick past generation 0"));
+        }
+        // Running the second-order step on the generations in reverse order
+        // gives the one before them.
+        // The step back uses the rule the step forward did, so the schedule
+        // goes back first.
+        self.generation -= 1;
+        self.follow_rule_schedule();
+        if self.previous.len() != self.cells.len() {
+            self.previous = vec![Cell::Dead; self.cells.len()];
+        }
+        std::mem::swap(&mut self.cells, &mut self.previous);
+        self.tick_second_order();
+        std::mem::swap(&mut self.cells, &mut self.previous);
+        Ok(())
+    }
+
+    /// Tick `delta` times if it is positive, or untick `-delta` times if it
+    /// is negative.
+    ///
+    /// Fails unless the update mode is `UpdateMode::SecondOrder`, so that a
+    /// scrub in either direction can be undone by one the other way. Also
+    /// fails, without moving, if it would go back past generation 0.
+    pub fn step_by(&mut self, delta: i64) -> Result<(), JsValue> {
+        if self.update_mode != UpdateMode::SecondOrder {
+            return Err(JsValue::from_str(
+                "step_by needs the second-order update mode",
+            ));
+        }
+        if delta < 0 && delta.unsigned_abs() > u64::from(self.generation) {
+            return Err(JsValue::from_str("can't step back past generation 0"));
+        }
+        if delta >= 0 {
+            for _ in 0..delta {
+                self.tick();
+            }
+        } else {
+            for _ in 0..delta.unsigned_abs() {
+                self.untick()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Render the cells as Unicode Braille, one character for each block of
+    /// 2 columns by 4 rows, with a newline after each line of characters.
+    ///
+    /// Blocks that run past the right or bottom edge are padded with dead
+    /// cells.
+    pub fn render_braille(&self) -> String {
+        // The dot bit for each `(row, column)` of a block.
+        const DOTS: [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+        let mut text = String::new();
+        for top in (0..self.height).step_by(4) {
+            for left in (0..self.width).step_by(2) {
+                let mut mask = 0;
+                for (dr, dots) in DOTS.iter().enumerate() {
+                    for (dc, &dot) in dots.iter().enumerate() {
+                        let (row, col) = (top + dr as u32, left + dc as u32);
+                        if row < self.height
+                            && col < self.width
+                            && self.cells[self.get_index(row, col)] == Cell::Alive
+                        {
+                            mask |= dot;
+                        }
+                    }
+                }
+                text.push(std::char::from_u32(0x2800 + mask).unwrap());
+            }
+            text.push('\n');
+        }
+        text
+    }
+
+    /// Get what the next tick does to each cell, without ticking: 0 if it
+    /// stays dead, 1 if it is born, 2 if it survives and 3 if it dies.
+    ///
+    /// This follows the synchronous rule, whatever the update mode.
+    pub fn outcome_grid(&self) -> Vec<u8> {
+        self.cells
+            .iter()
+            .zip(self.next_generation())
+            .map(|(&cell, next)| match (cell, next) {
+                (Cell::Dead, Cell::Dead) => 0,
+                (Cell::Dead, Cell::Alive) => 1,
+                (Cell::Alive, Cell::Alive) => 2,
+                (Cell::Alive, Cell::Dead) => 3,
+            })
+            .collect()
+    }
+
+    fn get_index(&self, row: u32, column: u32) -> usize {
+        (row * self.width + column) as usize
+    }
+
+    fn live_neighbor_count(&self, row: u32, column: u32) -> u8 {
+        let mut count = 0;
+        for delta_row in [-1, 0, 1].iter().cloned() {
+            for delta_col in [-1, 0, 1].iter().cloned() {
+                if delta_row == 0 && delta_col == 0 {
+                    continue;
+                }
+
+                let (neighbor_row, neighbor_col) =
+                    match self.neighbor(row, column, delta_row, delta_col) {
+                        Some(neighbor) => neighbor,
+                        None => continue,
+                    };
+                if !self.in_play_area(neighbor_row, neighbor_col) {
+                    continue;
+                }
+                let idx = self.get_index(neighbor_row, neighbor_col);
+                count += self.cells[idx] as u8;
+            }
+        }
+        count
+    }
+
+    /// Get the cell at an offset from `(row, column)` following the
+    /// universe's boundary, or `None` if it is past a dead edge.
+    fn offset(&self, row: u32, column: u32, delta_row: i64, delta_col: i64) -> Option<(u32, u32)> {
+        let (height, width) = (self.height as i64, self.width as i64);
+        let (r, c) = (row as i64 + delta_row, column as i64 + delta_col);
+        match self.boundary {
+            Boundary::Toroidal => Some((r.rem_euclid(height) as u32, c.rem_euclid(width) as u32)),
+            Boundary::Dead if r < 0 || r >= height || c < 0 || c >= width => None,
+            Boundary::Dead => Some((r as u32, c as u32)),
+        }
+    }
+
+    /// Get the neighbor at an offset from `(row, column)` as the rule sees
+    /// it, taking the wrap region into account.
+    fn neighbor(
+        &self,
+        row: u32,
+        column: u32,
+        delta_row: i64,
+        delta_col: i64,
+    ) -> Option<(u32, u32)> {
+        if let Some((top, left, height, width)) = self.wrap_region {
+            if rect_contains((top, left, height, width), row, column) {
+                let r = ((row - top) as i64 + delta_row).rem_euclid(height as i64) as u32;
+                let c = ((column - left) as i64 + delta_col).rem_euclid(width as i64) as u32;
+                return Some((top + r, left + c));
+            }
+        }
+        let (r, c) = self.offset(row, column, delta_row, delta_col)?;
+        match self.wrap_region {
+            Some(region) if rect_contains(region, r, c) => None,
+            _ => Some((r, c)),
+        }
+    }
+
+    fn next_cell(&self, row: u32, column: u32, live_neighbor_count: u8) -> Cell {
+        if !self.in_play_area(row, column) {
+            return Cell::Dead;
+        }
+        let cell = self.cells[self.get_index(row, column)];
+        self.rules[((row + column) % 2) as usize].next(cell, live_neighbor_count)
+    }
+
+    fn next_generation(&self) -> Vec<Cell> {
+        let mut next = self.cells.clone();
+
+        for row in 0..self.height {
+            for col in 0..self.width {
+                let idx = self.get_index(row, col);
+                let live_neighbor_count = self.live_neighbor_count(row, col);
+
+                next[idx] = self.next_cell(row, col, live_neighbor_count);
+            }
+        }
+
+        next
+    }
+
+    /// Advance one second-order generation: the rule's result, flipped
+    /// wherever the previous generation was alive.
+    fn tick_second_order(&mut self) {
+        if self.previous.len() != self.cells.len() {
+            self.previous = vec![Cell::Dead; self.cells.len()];
+        }
+        let mut next = self.next_generation();
+        for (cell, &before) in next.iter_mut().zip(self.previous.iter()) {
+            if before == Cell::Alive {
+                cell.toggle();
+            }
+        }
+        self.previous = std::mem::replace(&mut self.cells, next);
+    }
+
+    fn tick_random_async(&mut self) {
+        let mut order: Vec<u32> = (0..self.width * self.height).collect();
+        for i in (1..order.len()).rev() {
+            let j = self.rng.below(i as u64 + 1) as usize;
+            order.swap(i, j);
+        }
+
+        for idx in order {
+            let (row, col) = (idx / self.width, idx % self.width);
+            let live_neighbor_count = self.live_neighbor_count(row, col);
+            self.cells[idx as usize] = self.next_cell(row, col, live_neighbor_count);
+        }
+    }
+
+    /// Cells outside the play area are always dead, and count as dead
+    /// neighbors.
+    fn in_play_area(&self, row: u32, column: u32) -> bool {
+        self.play_area
+            .is_none_or(|area| rect_contains(area, row, column))
+    }
+
+    fn record_changes(&mut self) {
+        let changed = self.diff_cells(&self.recorded_frame);
+        push_u32(&mut self.recording, changed.len() as u32);
+        for idx in changed {
+            push_u32(&mut self.recording, idx);
+        }
+        self.recorded_frame.copy_from_slice(&self.cells);
+    }
+
+    fn grow_towards_live_edges(&mut self) {
+        if self.growth_margin == 0 || self.growth_remaining == 0 {
+            return;
+        }
+        let (min_row, min_col, max_row, max_col) = match self.bounding_box_of(&self.cells) {
+            Some(bounds) => bounds,
+            None => return,
+        };
+        let reached = [
+            min_row == 0,
+            max_col == self.width - 1,
+            max_row == self.height - 1,
+            min_col == 0,
+        ];
+        let mut amounts = [0; 4];
+        for (amount, &reached) in amounts.iter_mut().zip(reached.iter()) {
+            if reached {
+                *amount = self.growth_margin.min(self.growth_remaining);
+                self.growth_remaining -= *amount;
+            }
+        }
+        let [top, right, bottom, left] = amounts;
+        if top + right + bottom + left > 0 {
+            self.grow(top, right, bottom, left);
+        }
+    }
+
+    /// Add dead rows and columns around the edges, keeping the play area,
+    /// the wrap region and the pulsers on the same cells.
+    fn grow(&mut self, top: u32, right: u32, bottom: u32, left: u32) {
+        let width = self.width + left + right;
+        let height = self.height + top + bottom;
+        let mut cells = vec![Cell::Dead; (width * height) as usize];
+        for (row, col) in self.live_coordinates() {
+            cells[((row + top) * width + col + left) as usize] = Cell::Alive;
+        }
+        self.width = width;
+        self.height = height;
+        self.cells = cells;
+        self.stop_recording();
+        for rect in [&mut self.play_area, &mut self.wrap_region].iter_mut() {
+            if let Some((ref mut rect_top, ref mut rect_left, _, _)) = **rect {
+                *rect_top += top;
+                *rect_left += left;
+            }
+        }
+        for pulser in self.pulsers.iter_mut() {
+            pulser.0 += top;
+            pulser.1 += left;
+        }
+    }
+
+    /// Get a universe of the given size with every cell dead, sharing only
+    /// this one's rules, boundary and update mode.
+    fn blank_with_settings(&self, width: u32, height: u32) -> Universe {
+        let mut universe = Universe::new();
+        universe.set_width(width);
+        universe.set_height(height);
+        universe.rules = self.rules;
+        universe.boundary = self.boundary;
+        universe.set_update_mode(self.update_mode);
+        universe
+    }
+
+    /// Get the least and greatest offsets from `center`, along an axis of
+    /// `size` cells, that a disk of `radius` reaches, so that each cell is
+    /// visited once: past a dead edge there is nothing to reach, and around
+    /// a toroidal one the shortest offset to each cell is enough.
+    fn disk_span(&self, center: u32, radius: u32, size: u32) -> (i64, i64) {
+        let (center, radius, size) = (i64::from(center), i64::from(radius), i64::from(size));
+        match self.boundary {
+            Boundary::Toroidal if 2 * radius + 1 > size => (-(size / 2), (size - 1) / 2),
+            Boundary::Toroidal => (-radius, radius),
+            Boundary::Dead => ((-radius).max(-center), radius.min(size - 1 - center)),
+        }
+    }
+
+    /// Clip the play area and the wrap region to the universe after it
+    /// changes size, remove pulsers that are now off it and stop recording.
+    fn fit_to_size(&mut self) {
+        self.stop_recording();
+        let (height, width) = (self.height, self.width);
+        self.pulsers
+            .retain(|&(row, col, _, _)| row < height && col < width);
+        if let Some((top, left, height, width)) = self.wrap_region {
+            self.set_wrap_region(top, left, height, width);
+        }
+        if let Some((top, left, height, width)) = self.play_area {
+            let height = height.min(self.height.saturating_sub(top));
+            let width = width.min(self.width.saturating_sub(left));
+            self.set_play_area(top, left, height, width);
+        }
+    }
+
+    /// Set a cell, doing nothing if it is off the universe.
+    fn set_cell_clipped(&mut self, row: i64, col: i64, alive: bool) {
+        if row < 0 || col < 0 || row >= i64::from(self.height) || col >= i64::from(self.width) {
+            return;
+        }
+        let idx = self.get_index(row as u32, col as u32);
+        self.cells[idx] = if alive { Cell::Alive } else { Cell::Dead };
+        self.forget_age(idx);
+    }
+
+    fn fire_pulsers(&mut self) {
+        for i in 0..self.pulsers.len() {
+            let (row, col, period, phase) = self.pulsers[i];
+            if self.generation % period == phase {
+                let idx = self.get_index(row, col);
+                self.cells[idx] = Cell::Alive;
+            }
+        }
+    }
+
+    fn count_flips(&mut self, before: &[Cell]) {
+        if before.len() != self.cells.len() {
+            return;
+        }
+        if self.flip_count.len() != self.cells.len() {
+            self.flip_count = vec![0; self.cells.len()];
+        }
+        let cells = self.cells.iter().zip(before);
+        for (flips, (now, then)) in self.flip_count.iter_mut().zip(cells) {
+            if now != then {
+                *flips += 1;
+            }
+        }
+    }
+
+    fn age_cells(&mut self, before: &[Cell]) {
+        if self.ages.len() != self.cells.len() {
+            self.ages = vec![0; self.cells.len()];
+        }
+        if before.len() != self.cells.len() {
+            return;
+        }
+        for (age, (&now, &then)) in self.ages.iter_mut().zip(self.cells.iter().zip(before)) {
+            *age = if now == Cell::Alive && then == Cell::Alive {
+                age.saturating_add(1)
+            } else {
+                0
+            };
+        }
+    }
+
+    /// Start a cell's age over after it is set by hand.
+    fn forget_age(&mut self, idx: usize) {
+        if let Some(age) = self.ages.get_mut(idx) {
+            *age = 0;
+        }
+    }
+
+    fn kill_where_age<F: Fn(u16) -> bool>(&mut self, kill: F) {
+        for idx in 0..self.cells.len() {
+            let age = self.ages.get(idx).cloned().unwrap_or(0);
+            if self.cells[idx] == Cell::Alive && kill(age) {
+                self.cells[idx] = Cell::Dead;
+                if let Some(age) = self.ages.get_mut(idx) {
+                    *age = 0;
+                }
+            }
+        }
+    }
+
+    /// Apply the latest schedule entry reached by `generation`, or the rules
+    /// from before the schedule if none is reached yet.
+    fn follow_rule_schedule(&mut self) {
+        if self.rule_schedule.is_empty() {
+            return;
+        }
+        let generation = self.generation;
+        self.rules = match self
+            .rule_schedule
+            .iter()
+            .rev()
+            .find(|&&(g, _)| g <= generation)
+        {
+            Some(&(_, rule)) => [rule; 2],
+            None => self.unscheduled_rules,
+        };
+    }
+}
+
+impl Universe {
+    /// Get the dead and alive state of the entire universe.
+    pub fn get_cells(&self) -> &[Cell] {
+        &self.cells
+    }
+
+    /// Set cells to be alive in a universe by passing the row and column
+    /// of each cell as an array.
+    pub fn set_cells(&mut self, cells: &[(u32, u32)]) {
+        for (row, col) in cells.iter().cloned() {
+            let idx = self.get_index(row, col);
+            self.cells[idx] = Cell::Alive;
+            self.forget_age(idx);
+        }
+    }
+
+    /// Get how `tick` updates the cells.
+    pub fn update_mode(&self) -> UpdateMode {
+        self.update_mode
+    }
+
+    /// Set how `tick` updates the cells.
+    ///
+    /// Switching to `UpdateMode::RandomAsync` reseeds the update order, and
+    /// switching to `UpdateMode::SecondOrder` starts from an all-dead
+    /// previous generation.
+    pub fn set_update_mode(&mut self, mode: UpdateMode) {
+        match mode {
+            UpdateMode::RandomAsync { seed } => self.rng = Rng::new(seed),
+            UpdateMode::SecondOrder => self.previous.clear(),
+            UpdateMode::Synchronous => {}
+        }
+        self.update_mode = mode;
+    }
+
+    /// Get the `(row, column)` of every live cell, in row-major order.
+    fn live_coordinates(&self) -> Vec<(u32, u32)> {
+        (0..self.height)
+            .flat_map(|row| (0..self.width).map(move |col| (row, col)))
+            .filter(|&(row, col)| self.cells[self.get_index(row, col)] == Cell::Alive)
+            .collect()
+    }
+
+    /// Tick until the universe repeats an earlier state, for at most
+    /// `max_generations` ticks.
+    ///
+    /// Returns the generation at which the repeating cycle starts and its
+    /// period, or `None` if no repeat was seen. The universe is left at the
+    /// first repeated state.
+    fn detect_cycle(&mut self, max_generations: u32) -> Option<(u32, u32)> {
+        let mut seen: HashMap<Vec<Cell>, u32> = HashMap::new();
+        seen.insert(self.cells.clone(), 0);
+        for generation in 1..=max_generations {
+            self.tick();
+            if let Some(&start) = seen.get(&self.cells) {
+                return Some((start, generation - start));
+            }
+            seen.insert(self.cells.clone(), generation);
+        }
+        None
+    }
+
+    /// Tick until the universe settles into a still life or oscillation,
+    /// for at most `max_generations` ticks. Returns the number of ticks.
+    fn settle(&mut self, max_generations: u32) -> u32 {
+        match self.detect_cycle(max_generations) {
+            Some((start, period)) => start + period,
+            None => max_generations,
+        }
+    }
+
+    /// Get the groups of live cells that touch each other, including
+    /// diagonally, across the edges too under `Boundary::Toroidal`.
+    ///
+    /// Each group is given as points relative to one of its cells, so groups
+    /// that wrap around the edges of the universe keep their shape.
+    fn components(&self) -> Vec<Vec<(i64, i64)>> {
+        self.components_with_starts()
+            .into_iter()
+            .map(|(_, component)| component)
+            .collect()
+    }
+
+    /// Get the groups of live cells as `components` does, each with the
+    /// index of the cell its points are relative to.
+    fn components_with_starts(&self) -> Vec<(u32, Vec<(i64, i64)>)> {
+        let mut visited = vec![false; self.cells.len()];
+        let mut components = Vec::new();
+        for start in 0..self.cells.len() {
+            if visited[start] || self.cells[start] == Cell::Dead {
+                continue;
+            }
+            visited[start] = true;
+            let mut component = Vec::new();
+            let mut stack = vec![(start as u32 / self.width, start as u32 % self.width, 0, 0)];
+            while let Some((row, col, rel_row, rel_col)) = stack.pop() {
+                component.push((rel_row, rel_col));
+                for delta_row in [-1i64, 0, 1].iter().cloned() {
+                    for delta_col in [-1i64, 0, 1].iter().cloned() {
+                        if delta_row == 0 && delta_col == 0 {
+                            continue;
+                        }
+                        let (neighbor_row, neighbor_col) =
+                            match self.offset(row, col, delta_row, delta_col) {
+                                Some(neighbor) => neighbor,
+                                None => continue,
+                            };
+                        let idx = self.get_index(neighbor_row, neighbor_col);
+                        if visited[idx] || self.cells[idx] == Cell::Dead {
+                            continue;
+                        }
+                        visited[idx] = true;
+                        stack.push((
+                            neighbor_row,
+                            neighbor_col,
+                            rel_row + delta_row,
+                            rel_col + delta_col,
+                        ));
+                    }
+                }
+            }
+            components.push((start as u32, component));
+        }
+        components
+    }
+
+    /// Find isolated gliders, returning the cell indices of each one.
+    fn find_gliders(&self) -> Vec<Vec<usize>> {
+        let mut gliders = Vec::new();
+        if self.width < 5 || self.height < 5 {
+            return gliders;
+        }
+        let templates = pattern::glider_templates();
+        let wrap = |row: u32, col: u32, delta_row: i64, delta_col: i64| {
+            let row = (row as i64 + delta_row).rem_euclid(self.height as i64) as u32;
+            let col = (col as i64 + delta_col).rem_euclid(self.width as i64) as u32;
+            self.get_index(row, col)
+        };
+        for row in 0..self.height {
+            for col in 0..self.width {
+                for template in &templates {
+                    // The 3×3 window at (row, col) must match the template
+                    // exactly, and the ring of cells around it must be dead.
+                    let matches = (-1..4i64).all(|delta_row| {
+                        (-1..4i64).all(|delta_col| {
+                            let expected = template.contains(&(delta_row, delta_col));
+                            let idx = wrap(row, col, delta_row, delta_col);
+                            (self.cells[idx] == Cell::Alive) == expected
+                        })
+                    });
+                    if matches {
+                        gliders.push(
+                            template
+                                .iter()
+                                .map(|&(r, c)| wrap(row, col, r, c))
+                                .collect(),
+                        );
+                    }
+                }
+            }
+        }
+        gliders
+    }
+
+    /// Get the next generation, as a synchronous `tick` would compute it,
+    /// without advancing the universe.
+    pub fn peek_next(&self) -> Vec<Cell> {
+        self.next_generation()
+    }
+
+    /// Get the `(min_row, min_col, max_row, max_col)` of the live cells in
+    /// the next generation, without advancing the universe.
+    ///
+    /// Returns `None` if every cell will be dead.
+    pub fn next_bounding_box(&self) -> Option<(u32, u32, u32, u32)> {
+        self.bounding_box_of(&self.peek_next())
+    }
+
+    /// Get the `(min_row, min_col, max_row, max_col)` of the live cells in
+    /// `cells`, laid out like the cells of this universe.
+    fn bounding_box_of(&self, cells: &[Cell]) -> Option<(u32, u32, u32, u32)> {
+        let mut bounds: Option<(u32, u32, u32, u32)> = None;
+        for (idx, &cell) in cells.iter().enumerate() {
+            if cell == Cell::Dead {
+                continue;
+            }
+            let (row, col) = (idx as u32 / self.width, idx as u32 % self.width);
+            bounds = Some(match bounds {
+                None => (row, col, row, col),
+                Some((min_row, min_col, max_row, max_col)) => (
+                    min_row.min(row),
+                    min_col.min(col),
+                    max_row.max(row),
+                    max_col.max(col),
+                ),
+            });
+        }
+        bounds
+    }
+
+    /// Get the indices where `cells` differs from the current cells.
+    fn diff_cells(&self, cells: &[Cell]) -> Vec<u32> {
+        (0..self.cells.len())
+            .filter(|&idx| self.cells[idx] != cells[idx])
+            .map(|idx| idx as u32)
+            .collect()
+    }
+
+    /// Settle the universe, then count its separate oscillators by period.
+    ///
+    /// Each connected group of live cells is run on its own for up to
+    /// `max_period` ticks to find its period; still lifes have period 1.
+    /// Groups that don't return to their starting state in time, such as
+    /// spaceships, are left out. Returns `(period, count)` pairs sorted by
+    /// period. The universe is restored afterwards.
+    pub fn oscillator_census(&mut self, max_generations: u32, max_period: u32) -> Vec<(u32, u32)> {
+        let saved = self.clone();
+        self.settle(max_generations);
+        let mut census: Vec<(u32, u32)> = Vec::new();
+        for component in self.components() {
+            let mut isolated = self.isolate(&component, 4);
+            let start = isolated.cells.clone();
+            let period = (1..=max_period).find(|_| {
+                isolated.tick();
+                isolated.cells == start
+            });
+            if let Some(period) = period {
+                match census.iter_mut().find(|entry| entry.0 == period) {
+                    Some(entry) => entry.1 += 1,
+                    None => census.push((period, 1)),
+                }
+            }
+        }
+        *self = saved;
+        census.sort();
+        census
+    }
+
+    /// Put a pattern alone in a new universe with dead edges, `margin` dead
+    /// cells away from them, and the same rules as this one.
+    fn isolate(&self, points: &[(i64, i64)], margin: u32) -> Universe {
+        let points = pattern::normalize(points.iter().cloned());
+        let height = points.iter().map(|p| p.0).max().unwrap_or(0) as u32 + 1;
+        let width = points.iter().map(|p| p.1).max().unwrap_or(0) as u32 + 1;
+        let mut universe = Universe::new();
+        universe.set_width(width + 2 * margin);
+        universe.set_height(height + 2 * margin);
+        universe.set_boundary(Boundary::Dead);
+        universe.rules = self.rules;
+        let cells: Vec<(u32, u32)> = points
+            .iter()
+            .map(|&(row, col)| (row as u32 + margin, col as u32 + margin))
+            .collect();
+        universe.set_cells(&cells);
+        universe
+    }
+
+    /// Make each cell alive with probability `density`.
+    fn fill_random(&mut self, rng: &mut Rng, density: f64) {
+        for cell in self.cells.iter_mut() {
+            *cell = if rng.next_f64() < density {
+                Cell::Alive
+            } else {
+                Cell::Dead
+            };
+        }
+    }
+
+    /// Get, for each neighbor count 0 to 8, how many cells would change
+    /// their next state if the rule's bit for that count were flipped.
+    ///
+    /// A dead cell hangs on the birth bit for its neighbor count and a live
+    /// cell on the survival bit, so every cell the rule applies to is counted
+    /// once; cells outside the play area always die and are left out.
+    pub fn rule_sensitivity(&self) -> [u32; 9] {
+        let mut sensitivity = [0; 9];
+        for row in 0..self.height {
+            for col in 0..self.width {
+                if self.in_play_area(row, col) {
+                    sensitivity[self.live_neighbor_count(row, col) as usize] += 1;
+                }
+            }
+        }
+        sensitivity
+    }
+
+    fn parse_url_fragment(fragment: &str) -> Result<Universe, String> {
+        let fragment = fragment.trim_start_matches('#');
+        let parts: Vec<&str> = fragment.split(';').collect();
+        if parts.len() != 3 {
+            return Err(format!(
+                "expected 3 parts separated by ';', found {}",
+                parts.len()
+            ));
+        }
+        let rules: Vec<Rule> = parts[0]
+            .split(',')
+            .map(Rule::parse)
+            .collect::<Result<_, _>>()?;
+        let rules = match rules[..] {
+            [rule] => [rule; 2],
+            [even, odd] => [even, odd],
+            _ => return Err(format!("too many rules in '{}'", parts[0])),
+        };
+        let boundary = match parts[1] {
+            "t" => Boundary::Toroidal,
+            "d" => Boundary::Dead,
+            other => return Err(format!("unknown boundary '{}'", other)),
+        };
+        let bytes = base64::decode(parts[2]).ok_or("state is not valid base64")?;
+        let mut universe = Universe::from_bytes(&bytes).ok_or("state is malformed")?;
+        universe.rules = rules;
+        universe.boundary = boundary;
+        Ok(universe)
+    }
+
+    /// Get the `(row, column)` center of the cells weighted by how often
+    /// each has changed state, so a camera can follow where the action is
+    /// rather than where the live cells are.
+    ///
+    /// Changes are only counted while `set_track_flips` is on, from the last
+    /// `reset_flip_counts` or from when the universe was last resized.
+    /// Returns `None` if no cell has changed.
+    pub fn activity_centroid(&self) -> Option<(f64, f64)> {
+        if self.flip_count.len() != self.cells.len() {
+            return None;
+        }
+        let (mut total, mut row_sum, mut col_sum) = (0.0, 0.0, 0.0);
+        for (idx, &flips) in self.flip_count.iter().enumerate() {
+            let flips = f64::from(flips);
+            total += flips;
+            row_sum += flips * (idx as u32 / self.width) as f64;
+            col_sum += flips * (idx as u32 % self.width) as f64;
+        }
+        if total == 0.0 {
+            None
+        } else {
+            Some((row_sum / total, col_sum / total))
+        }
+    }
+
+    /// Iterate over the coming generations, as in
+    /// `for cells in universe.generations_iter().take(10)`.
+    ///
+    /// Each step ticks this universe, so it is left at the last generation
+    /// taken. The cells are yielded as copies, since an iterator's items
+    /// can't borrow from the universe it goes on to change.
+    pub fn generations_iter(&mut self) -> Generations<'_> {
+        Generations { universe: self }
+    }
+
+    /// Tick `generations` times, calling `f(generation, population)` after
+    /// each tick.
+    fn run_with<F: FnMut(u32, u32)>(&mut self, generations: u32, mut f: F) {
+        for _ in 0..generations {
+            self.tick();
+            f(self.generation, self.count_alive());
+        }
+    }
+
+    /// Compute the next generation under each of several rules, without
+    /// advancing the universe, for showing them side by side.
+    ///
+    /// The results are concatenated in order, each `width * height` bytes
+    /// long with one byte per cell (0 dead, 1 alive). Errors if any rule is
+    /// invalid.
+    pub fn next_states_for_rules(&self, rulestrings: &[String]) -> Result<Vec<u8>, JsValue> {
+        let rules: Vec<Rule> = rulestrings
+            .iter()
+            .map(|rulestring| Rule::parse(rulestring))
+            .collect::<Result<_, _>>()
+            .map_err(|e| JsValue::from_str(&e))?;
+        let mut board = self.clone();
+        let mut states = Vec::with_capacity(rules.len() * self.cells.len());
+        for rule in rules {
+            board.rules = [rule; 2];
+            states.extend(board.next_generation().into_iter().map(|cell| cell as u8));
+        }
+        Ok(states)
+    }
+
+    /// Get the cells, borrowed without copying while they are stored one
+    /// `Cell` each, as they are now.
+    ///
+    /// A more compact cell storage would have to hand out an owned copy
+    /// instead, which is why this is a `Cow` rather than a slice.
+    pub fn cells_cow(&self) -> Cow<'_, [Cell]> {
+        Cow::Borrowed(&self.cells)
+    }
+
+    /// Get the pairs of separate groups of live cells whose nearest cells
+    /// are at most `distance` apart, as measured by `component_distances`.
+    ///
+    /// Groups are numbered, and pairs listed, in the same order as there.
+    pub fn components_within(&self, distance: u32) -> Vec<(u32, u32)> {
+        let distances = self.component_distances();
+        let count = self.components().len() as u32;
+        let pairs = (0..count).flat_map(|i| (i + 1..count).map(move |j| (i, j)));
+        pairs
+            .zip(distances)
+            .filter(|&(_, d)| d <= distance as f32)
+            .map(|(pair, _)| pair)
+            .collect()
+    }
+}
+
+impl Default for Universe {
+    fn default() -> Universe {
+        Universe::new()
+    }
+}
+
+/// Parse a rule schedule for `Universe::set_rule_schedule`.
+fn parse_rule_schedule(schedule: &str) -> Result<Vec<(u32, Rule)>, String> {
+    let mut entries = Vec::new();
+    for entry in schedule.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+        let mut parts = entry.splitn(2, ':');
+        let generation = parts.next().unwrap_or("").trim();
+        let generation = generation
+            .parse()
+            .map_err(|_| format!("invalid generation `{}` in `{}`", generation, entry))?;
+        let rule = Rule::parse(parts.next().unwrap_or(""))?;
+        entries.push((generation, rule));
+    }
+    Ok(entries)
+}
+
+/// An iterator that ticks a universe on each call to `next` and yields a
+/// copy of the cells after the tick. Created by
+/// `Universe::generations_iter`.
+///
+/// It never ends; use `take` to bound it.
+pub struct Generations<'a> {
+    universe: &'a mut Universe,
+}
+
+impl<'a> Iterator for Generations<'a> {
+    type Item = Vec<Cell>;
+
+    fn next(&mut self) -> Option<Vec<Cell>> {
+        self.universe.tick();
+        Some(self.universe.cells.clone())
+    }
+}
+
+impl fmt::Display for Universe {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for line in self.cells.as_slice().chunks(self.width as usize) {
+            for &cell in line {
+                let symbol = if cell == Cell::Dead { '◻' } else { '◼' };
+                write!(f, "{}", symbol)?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Render several universes into one RGBA sprite sheet, `columns` to a row,
+/// each at `cell_size` pixels per cell and separated by `gap` transparent
+/// pixels.
+///
+/// Every board gets a slot as large as the largest board, with the board in
+/// its top-left corner; the rest of the slot, and any empty slots at the end
+/// of the last row, are transparent. The sheet is
+/// `columns * (slot_width + gap) - gap` pixels wide and
+/// `rows * (slot_height + gap) - gap` pixels high, where
+/// `rows = ceil(boards.len() / columns)`. Returns no pixels if there are no
+/// boards, no columns or a `cell_size` of 0.
+pub fn render_sheet_rgba(
+    boards: &[Universe],
+    columns: u32,
+    cell_size: u32,
+    gap: u32,
+    alive: u32,
+    dead: u32,
+) -> Vec<u8> {
+    if boards.is_empty() || columns == 0 || cell_size == 0 {
+        return Vec::new();
+    }
+    let columns = columns as usize;
+    let rows = boards.len().div_ceil(columns);
+    let gap = gap as usize;
+    let slot_width = boards.iter().map(|b| b.width).max().unwrap_or(0) as usize;
+    let slot_height = boards.iter().map(|b| b.height).max().unwrap_or(0) as usize;
+    let (slot_width, slot_height) = (
+        slot_width * cell_size as usize,
+        slot_height * cell_size as usize,
+    );
+    let sheet_width = columns * (slot_width + gap) - gap;
+    let sheet_height = rows * (slot_height + gap) - gap;
+    let mut pixels = vec![0; sheet_width * sheet_height * 4];
+    for (i, board) in boards.iter().enumerate() {
+        let image = board.render_rgba(cell_size, alive, dead);
+        let image_width = (board.width * cell_size) as usize;
+        if image_width == 0 {
+            continue;
+        }
+        let left = i % columns * (slot_width + gap);
+        let top = i / columns * (slot_height + gap);
+        for (y, line) in image.chunks(image_width * 4).enumerate() {
+            let dst = ((top + y) * sheet_width + left) * 4;
+            pixels[dst..dst + line.len()].copy_from_slice(line);
+        }
+    }
+    pixels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    fn get_init_universe() -> Universe {
+        let mut universe = Universe::new();
+        universe.set_width(5);
+        universe.set_height(5);
+        universe.set_cells(&[(1, 1), (2, 1), (3, 1)]);
+        universe
+    }
+
+    #[test]
+    fn it_should_work() {
+        assert_eq!(0, 0);
+    }
+
+    #[test]
+    fn it_should_get_index() {
+        let uni = get_init_universe();
+        assert_eq!(uni.get_index(1, 1), 6);
+        assert_eq!(uni.get_index(2, 1), 11);
+        assert_eq!(uni.get_index(3, 1), 16);
+    }
+
+    #[test]
+    fn it_should_count_alive_neighbors() {
+        let uni = get_init_universe();
+        assert_eq!(uni.live_neighbor_count(0, 0), 1);
+        assert_eq!(uni.live_neighbor_count(0, 1), 1);
+        assert_eq!(uni.live_neighbor_count(1, 0), 2);
+        assert_eq!(uni.live_neighbor_count(2, 1), 2);
+        assert_eq!(uni.live_neighbor_count(2, 0), 3);
+        assert_eq!(uni.live_neighbor_count(2, 2), 3);
+        assert_eq!(uni.live_neighbor_count(3, 3), 0);
+        assert_eq!(uni.live_neighbor_count(4, 1), 1);
+    }
+
+    #[test]
+    fn it_should_change_after_tick() {
+        let mut uni = get_init_universe();
+        assert_eq!(uni.cells[uni.get_index(1, 1)], Cell::Alive);
+        assert_eq!(uni.cells[uni.get_index(2, 1)], Cell::Alive);
+        assert_eq!(uni.cells[uni.get_index(3, 1)], Cell::Alive);
+        assert_eq!(uni.cells[uni.get_index(2, 0)], Cell::Dead);
+        assert_eq!(uni.cells[uni.get_index(2, 2)], Cell::Dead);
+        assert_eq!(uni.cells[uni.get_index(4, 2)], Cell::Dead);
+        uni.tick();
+        assert_eq!(uni.cells[uni.get_index(1, 1)], Cell::Dead);
+        assert_eq!(uni.cells[uni.get_index(2, 1)], Cell::Alive);
+        assert_eq!(uni.cells[uni.get_index(3, 1)], Cell::Dead);
+        assert_eq!(uni.cells[uni.get_index(2, 0)], Cell::Alive);
+        assert_eq!(uni.cells[uni.get_index(2, 2)], Cell::Alive);
+        assert_eq!(uni.cells[uni.get_index(4, 2)], Cell::Dead);
+    }
+
+    #[test]
+    fn it_should_render() {
+        let uni = get_init_universe();
+        let mut expected_str = String::from("");
+        for row in 0..5 {
+            for col in 0..5 {
+                match (row, col) {
+                    (1,1)|(2,1)|(3,1) => {
+                        expected_str.push('◼');
+                    },
+                    _ => {
+                        expected_str.push('◻');
+                    },
+                }
+                if col==4{
+                    expected_str.push('\n');
+                }
+            }
+        }
+        assert_eq!(uni.to_string(), expected_str);
+    }
+
+    #[test]
+    fn it_should_get_edge_density() {
+        let mut uni = get_init_universe();
+        assert_eq!(uni.edge_density(), 0.0);
+        let top_and_bottom: Vec<(u32, u32)> =
+            (0..5).flat_map(|col| vec![(0, col), (4, col)]).collect();
+        uni.set_cells(&top_and_bottom);
+        assert_eq!(uni.edge_density(), 10.0 / 16.0);
+        let sides: Vec<(u32, u32)> = (1..4).flat_map(|row| vec![(row, 0), (row, 4)]).collect();
+        uni.set_cells(&sides);
+        assert_eq!(uni.edge_density(), 1.0);
+    }
+
+    #[test]
+    fn it_should_minimap() {
+        let mut uni = get_init_universe();
+        uni.set_width(8);
+        uni.set_height(8);
+        assert_eq!(uni.minimap(4, 2), vec![0; 8]);
+        let all: Vec<(u32, u32)> = (0..8)
+            .flat_map(|row| (0..8).map(move |col| (row, col)))
+            .collect();
+        uni.set_cells(&all);
+        assert_eq!(uni.minimap(4, 2), vec![255; 8]);
+        assert_eq!(uni.minimap(16, 16), vec![255; 256]);
+    }
+
+    #[test]
+    fn it_should_get_transient_cells() {
+        let mut uni = get_init_universe();
+        assert_eq!(uni.transient_cells(2), vec![6, 10, 12, 16]);
+        assert_eq!(uni.transient_cells(1), Vec::<u32>::new());
+        assert_eq!(uni.get_cells(), get_init_universe().get_cells());
+    }
+
+    #[test]
+    fn it_should_tick_synchronously_by_default() {
+        let mut uni = get_init_universe();
+        assert_eq!(uni.update_mode(), UpdateMode::Synchronous);
+        let mut explicit = get_init_universe();
+        explicit.set_update_mode(UpdateMode::Synchronous);
+        for _ in 0..4 {
+            uni.tick();
+            explicit.tick();
+            assert_eq!(uni.get_cells(), explicit.get_cells());
+        }
+    }
+
+    #[test]
+    fn it_should_tick_async_reproducibly() {
+        let mut a = Universe::new();
+        let mut b = Universe::new();
+        a.set_update_mode(UpdateMode::RandomAsync { seed: 42 });
+        b.set_update_mode(UpdateMode::RandomAsync { seed: 42 });
+        let mut sync = Universe::new();
+        for _ in 0..5 {
+            a.tick();
+            b.tick();
+            sync.tick();
+        }
+        assert_eq!(a.get_cells(), b.get_cells());
+        assert_ne!(a.get_cells(), sync.get_cells());
+    }
+
+    #[test]
+    fn it_should_get_hull_area() {
+        let mut uni = get_init_universe();
+        assert_eq!(uni.hull_area(), 0.0);
+        uni.set_width(8);
+        uni.set_height(8);
+        uni.set_cells(&[(0, 0), (0, 4), (4, 0), (1, 1), (2, 1)]);
+        assert_eq!(uni.hull_area(), 8.0);
+        uni.set_cells(&[(4, 4)]);
+        assert_eq!(uni.hull_area(), 16.0);
+    }
+
+    #[test]
+    fn it_should_render_rgba() {
+        let uni = get_init_universe();
+        let pixels = uni.render_rgba(2, 0x0000_00ff, 0xffff_ffff);
+        assert_eq!(pixels.len(), 10 * 10 * 4);
+        // Cell (1, 1) covers pixels (2..4, 2..4).
+        let at = |x: usize, y: usize| &pixels[(y * 10 + x) * 4..(y * 10 + x) * 4 + 4];
+        assert_eq!(at(2, 3), &[0, 0, 0, 0xff]);
+        assert_eq!(at(1, 3), &[0xff, 0xff, 0xff, 0xff]);
+    }
+
+    #[test]
+    fn it_should_render_filmstrip() {
+        let mut uni = get_init_universe();
+        let (cell_size, gap) = (2, 3);
+        let strip = uni.filmstrip_rgba(3, cell_size, gap, 0x0000_00ff, 0xffff_ffff);
+        let board_px = 5 * cell_size as usize;
+        let strip_width = 3 * (board_px + gap as usize) - gap as usize;
+        assert_eq!(strip_width, 36);
+        assert_eq!(strip.len(), strip_width * board_px * 4);
+
+        let first = get_init_universe().render_rgba(cell_size, 0x0000_00ff, 0xffff_ffff);
+        for y in 0..board_px {
+            let row = &strip[y * strip_width * 4..(y * strip_width + board_px) * 4];
+            assert_eq!(row, &first[y * board_px * 4..(y + 1) * board_px * 4]);
+        }
+        assert_eq!(uni.get_cells(), get_init_universe().get_cells());
+    }
+
+    #[test]
+    fn it_should_count_settled_species() {
+        let mut uni = Universe::new();
+        uni.set_width(16);
+        uni.set_height(16);
+        uni.set_cells(&[(1, 1), (1, 2), (2, 1), (2, 2)]);
+        uni.set_cells(&[(1, 10), (1, 11), (2, 10), (2, 11)]);
+        uni.set_cells(&[(10, 5), (10, 6), (10, 7)]);
+        let before = uni.get_cells().to_vec();
+        assert_eq!(uni.settle_species(10), 2);
+        assert_eq!(uni.get_cells(), &before[..]);
+    }
+
+    #[test]
+    fn it_should_find_components_across_edges() {
+        let mut uni = get_init_universe();
+        uni.set_cells(&[(0, 4), (4, 4)]);
+        let mut sizes: Vec<usize> = uni.components().iter().map(|c| c.len()).collect();
+        sizes.sort();
+        assert_eq!(sizes, vec![2, 3]);
+    }
+
+    #[test]
+    fn it_should_remove_life_outside_play_area() {
+        let glider = [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)];
+        let mut free = Universe::new();
+        free.set_width(12);
+        free.set_height(12);
+        free.set_cells(&glider);
+        let mut walled = free.clone();
+        walled.set_play_area(0, 0, 6, 6);
+
+        for _ in 0..16 {
+            free.tick();
+            walled.tick();
+            for (row, col) in walled.live_coordinates() {
+                assert!(row < 6 && col < 6);
+            }
+        }
+        assert!(free
+            .live_coordinates()
+            .iter()
+            .any(|&(row, col)| row >= 6 && col >= 6));
+
+        walled.set_play_area(0, 0, 12, 12);
+        assert_eq!(walled.play_area, None);
+    }
+
+    #[test]
+    fn it_should_tick_with_identical_checkerboard_rules() {
+        let mut standard = Universe::new();
+        let mut checkered = Universe::new();
+        checkered.set_rule_checkerboard("B3/S23", "B3/S23");
+        for _ in 0..8 {
+            standard.tick();
+            checkered.tick();
+        }
+        assert_eq!(standard.get_cells(), checkered.get_cells());
+    }
+
+    #[test]
+    fn it_should_tick_with_distinct_checkerboard_rules() {
+        let mut standard = Universe::new();
+        let mut a = Universe::new();
+        let mut b = Universe::new();
+        a.set_rule_checkerboard("B3/S23", "B36/S23");
+        b.set_rule_checkerboard("B3/S23", "B36/S23");
+        b.set_rule_checkerboard("B3/S23", "not a rule");
+        for _ in 0..8 {
+            standard.tick();
+            a.tick();
+            b.tick();
+        }
+        assert_eq!(a.get_cells(), b.get_cells());
+        assert_ne!(a.get_cells(), standard.get_cells());
+    }
+
+    #[test]
+    fn it_should_render_rows() {
+        let uni = get_init_universe();
+        let rows = uni.render_rows('#', '.');
+        assert_eq!(rows.len(), 5);
+        assert!(rows.iter().all(|row| row.chars().count() == 5));
+        assert_eq!(rows[0], ".....");
+        assert_eq!(rows[2], ".#...");
+    }
+
+    #[test]
+    fn it_should_mask_gliders() {
+        let mut uni = Universe::new();
+        uni.set_width(16);
+        uni.set_height(16);
+        let glider = [(1, 2), (2, 3), (3, 1), (3, 2), (3, 3)];
+        // The same glider, rotated a quarter turn and moved.
+        let rotated = [(9, 10), (10, 10), (10, 12), (11, 10), (11, 11)];
+        uni.set_cells(&glider);
+        uni.set_cells(&rotated);
+        uni.set_cells(&[(12, 2), (12, 3), (13, 2), (13, 3)]);
+
+        let mask = uni.glider_mask();
+        let mut expected = vec![0; 256];
+        for &(row, col) in glider.iter().chain(rotated.iter()) {
+            expected[uni.get_index(row, col)] = 1;
+        }
+        assert_eq!(mask, expected);
+    }
+
+    #[test]
+    fn it_should_replay_a_recording() {
+        let mut uni = Universe::new();
+        uni.set_width(16);
+        uni.set_height(16);
+        uni.set_cells(&[
+            (1, 2),
+            (2, 3),
+            (3, 1),
+            (3, 2),
+            (3, 3),
+            (10, 10),
+            (10, 11),
+            (10, 12),
+        ]);
+        uni.start_recording();
+        for _ in 0..5 {
+            uni.tick();
+        }
+        uni.stop_recording();
+        uni.tick();
+
+        let mut replayed = Universe::new();
+        assert!(replayed.replay_recording(&uni.recording_bytes()));
+        assert_eq!(replayed.width(), 16);
+        assert_eq!(replayed.height(), 16);
+        uni.tick();
+        replayed.tick();
+        replayed.tick();
+        assert_eq!(replayed.get_cells(), uni.get_cells());
+
+        let bytes = uni.recording_bytes();
+        assert!(!replayed.replay_recording(&bytes[..bytes.len() - 1]));
+    }
+
+    #[test]
+    fn it_should_record_edits_between_ticks() {
+        let mut uni = get_init_universe();
+        uni.start_recording();
+        uni.tick();
+        uni.toggle_cell(0, 4);
+        uni.draw_rect(3, 3, 2, 2, true, true);
+        uni.tick();
+        uni.toggle_cell(4, 0);
+
+        let mut replayed = Universe::new();
+        assert!(replayed.replay_recording(&uni.recording_bytes()));
+        uni.toggle_cell(4, 0);
+        assert_eq!(replayed.get_cells(), uni.get_cells());
+
+        uni.set_directional_growth(2, 10);
+        uni.draw_rect(0, 0, 2, 2, true, true);
+        uni.tick();
+        assert!(!uni.is_recording);
+        assert!(replayed.replay_recording(&uni.recording_bytes()));
+        assert_eq!(replayed.width(), 5);
+    }
+
+    #[test]
+    fn it_should_get_spatial_autocorr() {
+        let mut uni = Universe::new();
+        uni.set_width(8);
+        uni.set_height(8);
+        let checkerboard: Vec<(u32, u32)> = (0..8)
+            .flat_map(|row| (0..8).map(move |col| (row, col)))
+            .filter(|&(row, col)| (row + col) % 2 == 0)
+            .collect();
+        uni.set_cells(&checkerboard);
+        assert_eq!(uni.spatial_autocorr(0, 1), -1.0);
+        assert_eq!(uni.spatial_autocorr(1, 1), 1.0);
+        assert_eq!(uni.spatial_autocorr(0, 0), 1.0);
+    }
+
+    #[test]
+    fn it_should_restore_rng_state() {
+        let mut uni = Universe::new();
+        uni.set_update_mode(UpdateMode::RandomAsync { seed: 7 });
+        for _ in 0..3 {
+            uni.tick();
+        }
+        let saved = uni.to_bytes();
+        let state = uni.rng_state();
+        let cells = uni.get_cells().to_vec();
+        for _ in 0..5 {
+            uni.tick();
+        }
+
+        let mut restored = Universe::from_bytes(&saved).unwrap();
+        assert_eq!(restored.rng_state(), state);
+        assert_eq!(restored.update_mode(), UpdateMode::RandomAsync { seed: 7 });
+        for _ in 0..5 {
+            restored.tick();
+        }
+        assert_eq!(restored.get_cells(), uni.get_cells());
+
+        let mut manual = Universe::new();
+        manual.set_update_mode(UpdateMode::RandomAsync { seed: 0 });
+        manual.cells = cells;
+        manual.set_rng_state(state);
+        for _ in 0..5 {
+            manual.tick();
+        }
+        assert_eq!(manual.get_cells(), uni.get_cells());
+
+        assert!(Universe::from_bytes(&saved[..saved.len() - 1]).is_none());
+        let mut huge = vec![0xff; 8];
+        huge.extend_from_slice(&[0; 17]);
+        assert!(Universe::from_bytes(&huge).is_none());
+    }
+
+    #[test]
+    fn it_should_get_next_bounding_box() {
+        let mut uni = Universe::new();
+        uni.set_width(12);
+        uni.set_height(12);
+        uni.set_cells(&[(1, 2), (2, 3), (3, 1), (3, 2), (3, 3)]);
+        let start = uni.bounding_box_of(uni.get_cells());
+        assert_eq!(start, Some((1, 1, 3, 3)));
+        assert_eq!(uni.next_bounding_box(), Some((2, 1, 4, 3)));
+
+        for _ in 0..4 {
+            let next = uni.next_bounding_box();
+            assert_eq!(uni.peek_next(), {
+                let mut ticked = uni.clone();
+                ticked.tick();
+                ticked.cells
+            });
+            uni.tick();
+            assert_eq!(uni.bounding_box_of(uni.get_cells()), next);
+        }
+        assert_eq!(uni.bounding_box_of(uni.get_cells()), Some((2, 2, 4, 4)));
+
+        let blinker = get_init_universe();
+        assert_eq!(blinker.next_bounding_box(), Some((2, 0, 2, 2)));
+        uni.set_width(12);
+        assert_eq!(uni.next_bounding_box(), None);
+    }
+
+    #[test]
+    fn it_should_compose_patterns() {
+        let mut uni = Universe::new();
+        uni.set_width(16);
+        uni.set_height(16);
+        let placed = uni.compose("glider,1,1\nnot-a-pattern,5,5; glider, 8, 9;block,x,1");
+        assert_eq!(placed, 2);
+
+        let mut expected = Universe::new();
+        expected.set_width(16);
+        expected.set_height(16);
+        expected.set_cells(&[(1, 2), (2, 3), (3, 1), (3, 2), (3, 3)]);
+        expected.set_cells(&[(8, 10), (9, 11), (10, 9), (10, 10), (10, 11)]);
+        assert_eq!(uni.get_cells(), expected.get_cells());
+        assert_eq!(uni.find_gliders().len(), 2);
+
+        uni.set_width(16);
+        assert_eq!(uni.compose("block,4294967295,4294967295"), 1);
+        assert_eq!(
+            uni.live_coordinates(),
+            vec![(0, 0), (0, 15), (15, 0), (15, 15)]
+        );
+        assert!(uni.matches_pattern_at(u32::MAX, u32::MAX, 2, &[1, 1, 1, 1]));
+
+        uni.set_width(0);
+        assert!(!uni.spawn_named("block", 0, 0));
+        assert_eq!(uni.compose("block,0,0"), 0);
+        assert!(!uni.matches_pattern_at(0, 0, 2, &[1, 1, 1, 1]));
+        assert!(uni.matches_pattern_at(0, 0, 2, &[]));
+    }
+
+    #[test]
+    fn it_should_point_density_gradient_at_clusters() {
+        let mut uni = Universe::new();
+        uni.set_width(16);
+        uni.set_height(16);
+        uni.set_cells(&[(7, 7), (7, 8), (8, 7), (8, 8)]);
+        let gradient = uni.density_gradient(5);
+        assert_eq!(gradient.len(), 2 * 256);
+        let at = |row: u32, col: u32| {
+            let idx = uni.get_index(row, col) * 2;
+            (gradient[idx], gradient[idx + 1])
+        };
+        let (drow, dcol) = at(3, 8);
+        assert!(drow > 0 && drow > dcol.abs());
+        let (drow, dcol) = at(7, 12);
+        assert!(dcol < 0 && -dcol > drow.abs());
+        let (drow, dcol) = at(12, 3);
+        assert!(drow < 0 && dcol > 0);
+        assert_eq!(at(0, 0), (0, 0));
+    }
+
+    #[test]
+    fn it_should_evolve_until_pattern() {
+        let mut uni = Universe::new();
+        uni.set_width(12);
+        uni.set_height(12);
+        uni.set_cells(&[(5, 5), (5, 6), (6, 5)]);
+        #[rustfmt::skip]
+        let block = [
+            0, 0, 0, 0,
+            0, 1, 1, 0,
+            0, 1, 1, 0,
+            0, 0, 0, 0,
+        ];
+        assert!(!uni.matches_pattern_at(4, 4, 4, &block));
+        assert_eq!(uni.evolve_until_pattern(4, &block, 10), Some(1));
+        assert_eq!(uni.live_coordinates(), vec![(5, 5), (5, 6), (6, 5)]);
+
+        let blinker: Vec<u8> = get_init_universe()
+            .get_cells()
+            .iter()
+            .map(|&c| c as u8)
+            .collect();
+        assert_eq!(uni.evolve_until_pattern(5, &blinker, 10), None);
+    }
+
+    #[test]
+    fn it_should_round_trip_rle_bytes() {
+        let mut uni = Universe::new();
+        uni.set_width(40);
+        uni.set_height(10);
+        uni.spawn_named("glider", 1, 1);
+        let full_row: Vec<(u32, u32)> = (0..40).map(|col| (7, col)).collect();
+        uni.set_cells(&full_row);
+
+        let bytes = uni.to_rle_bytes();
+        assert!(bytes.len() < uni.get_cells().len());
+        let decoded = Universe::from_rle_bytes(&bytes).unwrap();
+        assert_eq!(decoded.width(), 40);
+        assert_eq!(decoded.height(), 10);
+        assert_eq!(decoded.get_cells(), uni.get_cells());
+
+        let decoded = Universe::from_rle_bytes(&Universe::new().to_rle_bytes()).unwrap();
+        assert_eq!(decoded.get_cells(), Universe::new().get_cells());
+
+        assert!(Universe::from_rle_bytes(&bytes[..bytes.len() - 1]).is_none());
+        assert!(Universe::from_rle_bytes(&[1, 0, 0, 0, 1, 0, 0, 0, 2]).is_none());
+        assert!(Universe::from_rle_bytes(&[0xff; 8]).is_none());
+        assert!(Universe::from_rle_bytes(&[0, 0, 1, 0, 0, 0, 1, 0, 255]).is_none());
+    }
+
+    #[test]
+    fn it_should_diff_snapshot() {
+        let mut uni = get_init_universe();
+        uni.save_snapshot("start");
+        assert_eq!(uni.diff_snapshot("start"), Vec::<u32>::new());
+        uni.tick();
+        assert_eq!(uni.diff_snapshot("start"), vec![6, 10, 12, 16]);
+        assert_eq!(uni.diff_snapshot("unknown"), Vec::<u32>::new());
+        uni.set_width(6);
+        assert_eq!(uni.diff_snapshot("start"), Vec::<u32>::new());
+    }
+
+    #[test]
+    fn it_should_wrap_gliders_inside_wrap_region() {
+        let mut uni = Universe::new();
+        uni.set_width(20);
+        uni.set_height(20);
+        uni.set_boundary(Boundary::Dead);
+        uni.set_wrap_region(0, 0, 8, 8);
+        uni.spawn_named("glider", 2, 2);
+        uni.spawn_named("glider", 10, 10);
+        let inside: Vec<(u32, u32)> = uni
+            .live_coordinates()
+            .into_iter()
+            .filter(|&(row, col)| row < 8 && col < 8)
+            .collect();
+
+        // On an 8×8 torus the glider is back where it started after 32
+        // generations, having crossed the region's seams twice.
+        for _ in 0..32 {
+            uni.tick();
+        }
+        let live = uni.live_coordinates();
+        let now_inside: Vec<(u32, u32)> = live
+            .iter()
+            .cloned()
+            .filter(|&(row, col)| row < 8 && col < 8)
+            .collect();
+        assert_eq!(now_inside, inside);
+
+        // The other glider crashed into the dead edges and became a block.
+        let outside: Vec<(u32, u32)> = live
+            .iter()
+            .cloned()
+            .filter(|&(row, col)| row >= 8 || col >= 8)
+            .collect();
+        assert_eq!(outside, vec![(18, 18), (18, 19), (19, 18), (19, 19)]);
+    }
+
+    #[test]
+    fn it_should_not_wrap_with_dead_boundary() {
+        let mut uni = get_init_universe();
+        uni.set_cells(&[(0, 4), (4, 4)]);
+        assert_eq!(uni.live_neighbor_count(0, 0), 3);
+        assert_eq!(uni.components().len(), 2);
+        uni.set_boundary(Boundary::Dead);
+        assert_eq!(uni.live_neighbor_count(0, 0), 1);
+        assert_eq!(uni.components().len(), 3);
+    }
+
+    #[test]
+    fn it_should_get_golly_hash() {
+        let mut uni = Universe::new();
+        uni.set_width(16);
+        uni.set_height(16);
+        assert_eq!(uni.golly_hash(), 31_415_962);
+        uni.set_cells(&[(3, 3)]);
+        assert_eq!(uni.golly_hash(), 529_404_522);
+
+        // Golly's script API would return this as the signed -1009722861.
+        uni.set_width(16);
+        uni.spawn_named("glider", 5, 9);
+        assert_eq!(uni.golly_hash(), 3_285_244_435);
+        assert_eq!(uni.golly_hash() as i32, -1_009_722_861);
+    }
+
+    #[test]
+    fn it_should_grow_towards_traveling_gliders() {
+        let mut uni = Universe::new();
+        uni.set_width(10);
+        uni.set_height(10);
+        uni.set_boundary(Boundary::Dead);
+        uni.set_directional_growth(4, 100);
+        uni.spawn_named("glider", 1, 1);
+        let mut big = Universe::new();
+        big.set_width(40);
+        big.set_height(40);
+        big.set_boundary(Boundary::Dead);
+        big.spawn_named("glider", 1, 1);
+
+        for _ in 0..40 {
+            uni.tick();
+            big.tick();
+        }
+        // Only the bottom and right edges grew, so coordinates still line up.
+        assert!(uni.width() > 10 && uni.height() > 10);
+        assert!(uni.width() < 40 && uni.height() < 40);
+        assert_eq!(uni.live_coordinates(), big.live_coordinates());
+        assert_eq!(uni.find_gliders().len(), 1);
+
+        let mut capped = Universe::new();
+        capped.set_width(10);
+        capped.set_height(10);
+        capped.set_boundary(Boundary::Dead);
+        capped.set_directional_growth(4, 6);
+        capped.spawn_named("glider", 1, 1);
+        for _ in 0..40 {
+            capped.tick();
+        }
+        assert_eq!(capped.width() + capped.height(), 26);
+    }
+
+    #[test]
+    fn it_should_take_oscillator_census() {
+        let mut uni = Universe::new();
+        uni.set_width(24);
+        uni.set_height(24);
+        uni.spawn_named("blinker", 2, 2);
+        uni.spawn_named("blinker", 10, 10);
+        uni.spawn_named("block", 2, 15);
+        uni.spawn_named("glider", 16, 2);
+        let before = uni.get_cells().to_vec();
+        assert_eq!(uni.oscillator_census(10, 8), vec![(1, 1), (2, 2)]);
+        assert_eq!(uni.get_cells(), &before[..]);
+    }
+
+    #[test]
+    fn it_should_precompute_next() {
+        let mut uni = Universe::new();
+        uni.precompute_next();
+        let next = unsafe { std::slice::from_raw_parts(uni.next_cells_ptr(), 64 * 64) };
+        assert_eq!(next, &uni.peek_next()[..]);
+        assert_ne!(next, uni.get_cells());
+        assert_eq!(uni.get_cells(), Universe::new().get_cells());
+    }
+
+    #[test]
+    fn it_should_create_from_grayscale() {
+        // A left-to-right gradient from 0 to 224.
+        let pixels: Vec<u8> = (0..4).flat_map(|_| (0..8).map(|x| x * 32)).collect();
+        let uni = Universe::from_grayscale(8, 4, &pixels, 128).ok().unwrap();
+        assert_eq!(uni.width(), 8);
+        assert_eq!(uni.height(), 4);
+        let rows = uni.render_rows('#', '.');
+        assert!(rows.iter().all(|row| row == "....####"));
+    }
+
+    #[test]
+    fn it_should_get_moment_of_inertia() {
+        let mut block = Universe::new();
+        block.set_width(16);
+        block.set_height(16);
+        assert_eq!(block.moment_of_inertia(), 0.0);
+        block.set_cells(&[(5, 5)]);
+        assert_eq!(block.moment_of_inertia(), 0.0);
+        block.spawn_named("block", 5, 5);
+        assert_eq!(block.moment_of_inertia(), 2.0);
+
+        let mut dispersed = Universe::new();
+        dispersed.set_width(16);
+        dispersed.set_height(16);
+        dispersed.set_cells(&[(0, 0), (0, 10), (10, 0), (10, 10)]);
+        assert_eq!(dispersed.moment_of_inertia(), 200.0);
+    }
+
+    #[test]
+    fn it_should_summarize_soups() {
+        let mut uni = get_init_universe();
+        let json = uni.batch_soup_stats(3, 12, 1, 0.4, 200);
+        assert!(json.starts_with("{\"trials\":3,\"population\":{\"mean\":"));
+        let field = |object: &str, key: &str| -> f64 {
+            let object = &json[json.find(object).unwrap()..];
+            let start = object.find(&format!("\"{}\":", key)).unwrap() + key.len() + 3;
+            let end = start + object[start..].find(&[',', '}'][..]).unwrap();
+            object[start..end].parse().unwrap()
+        };
+        let (mean, min, max) = (
+            field("population", "mean"),
+            field("population", "min"),
+            field("population", "max"),
+        );
+        assert!(min <= mean && mean <= max && max <= 144.0);
+        assert!(field("lifespan", "max") <= 200.0);
+        assert_eq!(json, uni.batch_soup_stats(3, 12, 1, 0.4, 200));
+        assert_eq!(uni.get_cells(), get_init_universe().get_cells());
+
+        let mut dressed = Universe::new();
+        dressed.set_wrap_region(0, 0, 64, 64);
+        dressed.set_play_area(0, 0, 4, 4);
+        dressed.set_directional_growth(4, 40);
+        dressed.set_pulser(50, 50, 1, 0);
+        dressed.set_rule_schedule("1:B/S");
+        let mut plain = Universe::new();
+        assert_eq!(
+            dressed.batch_soup_stats(2, 8, 5, 0.4, 50),
+            plain.batch_soup_stats(2, 8, 5, 0.4, 50)
+        );
+    }
+
+    #[test]
+    fn it_should_verify_spaceships() {
+        let mut uni = Universe::new();
+        uni.set_width(12);
+        uni.set_height(12);
+        uni.spawn_named("glider", 2, 2);
+        assert!(uni.verify_spaceship(4, 1, 1));
+        assert!(!uni.verify_spaceship(4, -1, 1));
+        assert!(!uni.verify_spaceship(2, 1, 1));
+        assert!(uni.verify_spaceship(8, 2, 2));
+
+        uni.spawn_named("glider", 9, 9);
+        assert!(uni.verify_spaceship(4, 1, 1));
+        uni.set_boundary(Boundary::Dead);
+        assert!(!uni.verify_spaceship(4, 1, 1));
+        assert!(!uni.verify_spaceship(4, i32::MIN, i32::MIN));
+    }
+
+    #[test]
+    fn it_should_render_svg() {
+        let uni = get_init_universe();
+        let svg = uni.to_svg(4, "black", "#fff");
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.contains("viewBox=\"0 0 20 20\""));
+        assert!(svg.contains("<rect width=\"20\" height=\"20\" fill=\"#fff\"/>"));
+        assert_eq!(svg.matches("fill=\"black\"").count(), 3);
+        assert!(svg.contains("<rect x=\"4\" y=\"8\" width=\"4\" height=\"4\" fill=\"black\"/>"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert!(uni
+            .to_svg(1, "\"><script>", "red")
+            .contains("fill=\"&quot;&gt;&lt;script&gt;\""));
+    }
+
+    #[test]
+    fn it_should_open_pattern() {
+        let mut uni = Universe::new();
+        uni.open_pattern("x = 3, y = 3\nbob$2bo$3o!", 5)
+            .ok()
+            .unwrap();
+        assert_eq!(uni.width(), 13);
+        assert_eq!(uni.height(), 13);
+        assert_eq!(
+            uni.live_coordinates(),
+            vec![(5, 6), (6, 7), (7, 5), (7, 6), (7, 7)]
+        );
+        assert_eq!(uni.bounding_box_of(uni.get_cells()), Some((5, 5, 7, 7)));
+
+        let mut dressed = Universe::new();
+        dressed.set_wrap_region(0, 0, 64, 64);
+        dressed.set_play_area(20, 20, 10, 10);
+        dressed.set_pulser(60, 60, 2, 0);
+        dressed.full_sync();
+        dressed.set_rule_schedule("3:B/S");
+        dressed
+            .open_pattern("x = 3, y = 3\nbob$2bo$3o!", 5)
+            .ok()
+            .unwrap();
+        assert_eq!((dressed.wrap_region, dressed.play_area), (None, None));
+        assert!(dressed.pulsers.is_empty() && dressed.rule_schedule.is_empty());
+        assert!(dressed.delta_since_last_sync().is_empty());
+        dressed.tick_n(4);
+        uni.tick_n(4);
+        assert_eq!(dressed.get_cells(), uni.get_cells());
+    }
+
+    #[test]
+    fn it_should_open_pattern_while_recording() {
+        let mut uni = get_init_universe();
+        uni.set_track_ages(true);
+        uni.set_track_flips(true);
+        uni.start_recording();
+        uni.tick_n(3);
+        let recording = uni.recording_bytes();
+        uni.open_pattern("x = 3, y = 3\nbo$bo$bo!", 1).ok().unwrap();
+        assert!(!uni.is_recording);
+        assert_eq!(uni.generation(), 0);
+        assert!(uni.flip_count.is_empty());
+        for row in 0..5 {
+            for col in 0..5 {
+                assert_eq!(uni.age(row, col), 0);
+            }
+        }
+        uni.tick();
+        assert_eq!(uni.generation(), 1);
+        assert_eq!(uni.recording_bytes(), recording);
+    }
+
+    #[test]
+    fn it_should_get_rule_sensitivity() {
+        let mut uni = get_init_universe();
+        let sensitivity = uni.rule_sensitivity();
+        // Only the two cells beside the blinker's middle hang on B3.
+        assert_eq!(sensitivity[3], 2);
+        assert_eq!(sensitivity[2], 5);
+        assert_eq!(sensitivity.iter().sum::<u32>(), 25);
+
+        uni.set_play_area(0, 1, 5, 4);
+        assert_eq!(uni.rule_sensitivity()[3], 1);
+    }
+
+    #[test]
+    fn it_should_suggest_chunk_sizes() {
+        let mut uni = get_init_universe();
+        let tiny = uni.suggest_chunk_size();
+        uni.set_width(64);
+        uni.set_height(64);
+        let medium = uni.suggest_chunk_size();
+        uni.set_width(1024);
+        uni.set_height(1024);
+        let huge = uni.suggest_chunk_size();
+        assert!(tiny > medium && medium > huge);
+        assert_eq!(tiny, 100);
+        assert_eq!(huge, 1);
+
+        let mut ticked = get_init_universe();
+        let mut stepped = get_init_universe();
+        ticked.tick_n(3);
+        for _ in 0..3 {
+            stepped.tick();
+        }
+        assert_eq!(ticked.get_cells(), stepped.get_cells());
+    }
+
+    #[test]
+    fn it_should_count_memory_bytes() {
+        let mut uni = Universe::new();
+        let base = uni.memory_bytes();
+        assert!(base >= 64 * 64);
+        uni.precompute_next();
+        assert_eq!(uni.memory_bytes(), base + uni.next.capacity());
+        let with_next = uni.memory_bytes();
+        uni.set_track_ages(true);
+        uni.tick();
+        assert_eq!(
+            uni.memory_bytes(),
+            with_next + 64 * 64 * std::mem::size_of::<u16>()
+        );
+        let with_ages = uni.memory_bytes();
+        uni.save_snapshot("a");
+        assert!(uni.memory_bytes() > with_ages + 64 * 64);
+    }
+
+    #[test]
+    fn it_should_explode_a_disk() {
+        let mut uni = Universe::new();
+        let before = uni.get_cells().to_vec();
+        uni.explode(10, 10, 4, 99, 0.5);
+        for row in 0..64 {
+            for col in 0..64 {
+                let (dr, dc) = (row as i64 - 10, col as i64 - 10);
+                let idx = uni.get_index(row, col);
+                if dr * dr + dc * dc > 16 {
+                    assert_eq!(uni.get_cells()[idx], before[idx]);
+                }
+            }
+        }
+        assert_ne!(uni.get_cells(), &before[..]);
+
+        let mut again = Universe::new();
+        again.explode(10, 10, 4, 99, 0.5);
+        assert_eq!(again.get_cells(), uni.get_cells());
+
+        let mut clipped = Universe::new();
+        clipped.set_boundary(Boundary::Dead);
+        clipped.explode(0, 0, 3, 1, 1.0);
+        assert_eq!(clipped.get_cells()[clipped.get_index(63, 63)], before[4095]);
+        assert_eq!(clipped.get_cells()[clipped.get_index(3, 0)], Cell::Alive);
+
+        clipped.explode(63, 63, u32::MAX, 1, 1.0);
+        assert_eq!(clipped.count_alive(), 64 * 64);
+        let mut wrapped = Universe::new();
+        wrapped.set_width(9);
+        wrapped.set_height(8);
+        wrapped.explode(u32::MAX, u32::MAX, u32::MAX, 1, 0.0);
+        assert_eq!(wrapped.count_alive(), 0);
+        wrapped.explode(2, 2, 6, 1, 1.0);
+        assert_eq!(wrapped.count_alive(), 72);
+        wrapped.explode(2, 2, 4, 1, 0.0);
+        assert_eq!(wrapped.count_alive(), 72 - 48);
+    }
+
+    #[test]
+    fn it_should_measure_rule_distance() {
+        let mut uni = Universe::new();
+        uni.set_width(24);
+        uni.set_height(24);
+        assert_eq!(
+            uni.rule_distance("B3/S23", "B3/S23", 3, 10, 5).ok(),
+            Some(0.0)
+        );
+        let distance = uni
+            .rule_distance("B3/S23", "B36/S23", 3, 10, 5)
+            .ok()
+            .unwrap();
+        assert!(distance > 0.0 && distance <= 1.0);
+        assert_eq!(uni.count_alive(), 0);
+
+        uni.set_directional_growth(4, 100);
+        uni.set_rule_schedule("2:B/S");
+        assert_eq!(
+            uni.rule_distance("B3/S23", "B36/S23", 3, 10, 5).ok(),
+            Some(distance)
+        );
+    }
+
+    #[test]
+    fn it_should_get_live_spans() {
+        let mut uni = get_init_universe();
+        assert_eq!(uni.live_spans(), vec![1, 1, 1, 2, 1, 1, 3, 1, 1]);
+        uni.set_width(5);
+        let row: Vec<(u32, u32)> = (0..5).map(|col| (2, col)).collect();
+        uni.set_cells(&row);
+        uni.set_cells(&[(4, 1), (4, 2), (4, 4)]);
+        assert_eq!(uni.live_spans(), vec![2, 0, 5, 4, 1, 2, 4, 4, 1]);
+    }
+
+    #[test]
+    fn it_should_switch_rule_presets() {
+        let mut uni = Universe::new();
+        assert_eq!(uni.rule_string(), "B3/S23");
+        assert!(uni.store_rule_preset(0, "B3/S23"));
+        assert!(uni.store_rule_preset(1, "B36/S23"));
+        assert!(!uni.store_rule_preset(2, "B36/S2x"));
+        assert!(!uni.store_rule_preset(4, "B3/S23"));
+
+        assert!(uni.apply_rule_preset(1));
+        assert_eq!(uni.rule_string(), "B36/S23");
+        assert!(uni.apply_rule_preset(0));
+        assert_eq!(uni.rule_string(), "B3/S23");
+        assert!(!uni.apply_rule_preset(2));
+        assert!(!uni.apply_rule_preset(9));
+        assert_eq!(uni.rule_string(), "B3/S23");
+
+        uni.set_rule_checkerboard("B3/S23", "B36/S23");
+        assert_eq!(uni.rule_string(), "B3/S23,B36/S23");
+    }
+
+    #[test]
+    fn it_should_find_board_period() {
+        let mut uni = Universe::new();
+        uni.set_width(24);
+        uni.set_height(19);
+        let mut cells = vec![(1, 21), (2, 21), (3, 21)];
+        for &line in [0, 5, 7, 12].iter() {
+            for &along in [2, 3, 4, 8, 9, 10].iter() {
+                cells.push((line + 3, along + 3));
+                cells.push((along + 3, line + 3));
+            }
+        }
+        uni.set_cells(&cells);
+        let before = uni.get_cells().to_vec();
+
+        assert_eq!(uni.board_period(10), 6);
+        assert_eq!(uni.get_cells(), &before[..]);
+        assert_eq!(uni.board_period(5), 0);
+    }
+
+    #[test]
+    fn it_should_draw_line() {
+        let mut uni = Universe::new();
+        uni.set_width(6);
+        uni.set_height(6);
+        uni.draw_line(4, 0, 0, 4, true);
+        assert_eq!(
+            uni.live_coordinates(),
+            vec![(0, 4), (1, 3), (2, 2), (3, 1), (4, 0)]
+        );
+
+        uni.draw_line(2, 0, 2, 9, true);
+        assert_eq!(uni.count_alive(), 10);
+        uni.draw_line(2, 0, 2, 9, false);
+        assert_eq!(uni.live_coordinates(), vec![(0, 4), (1, 3), (3, 1), (4, 0)]);
+
+        let mut uni = Universe::new();
+        uni.set_width(6);
+        uni.set_height(6);
+        uni.draw_line(0, 0, 5, 2, true);
+        assert_eq!(
+            uni.live_coordinates(),
+            vec![(0, 0), (1, 0), (2, 1), (3, 1), (4, 2), (5, 2)]
+        );
+        uni.draw_line(0, 0, 5, 2, false);
+        uni.draw_line(5, 1, 0, 4, true);
+        assert_eq!(
+            uni.live_coordinates(),
+            vec![(0, 4), (1, 3), (2, 3), (3, 2), (4, 2), (5, 1)]
+        );
+
+        uni.draw_line(u32::MAX, u32::MAX, 0, 0, true);
+        assert_eq!(uni.count_alive(), 12);
+        uni.draw_line(3_000_000_000, 0, 0, 5, true);
+        assert_eq!(uni.get_cells()[uni.get_index(0, 5)], Cell::Alive);
+    }
+
+    #[test]
+    fn it_should_draw_filled_rect() {
+        let mut uni = Universe::new();
+        uni.set_width(5);
+        uni.set_height(5);
+        uni.draw_rect(3, 3, 4, 4, true, true);
+        assert_eq!(uni.live_coordinates(), vec![(3, 3), (3, 4), (4, 3), (4, 4)]);
+        uni.draw_rect(0, 0, 0, 3, true, true);
+        assert_eq!(uni.count_alive(), 4);
+        uni.draw_rect(0, 0, u32::MAX, u32::MAX, true, true);
+        assert_eq!(uni.count_alive(), 25);
+    }
+
+    #[test]
+    fn it_should_draw_outlined_rect() {
+        let mut uni = Universe::new();
+        uni.set_width(5);
+        uni.set_height(5);
+        uni.draw_rect(0, 0, 5, 5, true, true);
+        uni.draw_rect(1, 1, 3, 3, false, false);
+        assert_eq!(uni.count_alive(), 17);
+        assert_eq!(uni.get_cells()[uni.get_index(2, 2)], Cell::Alive);
+        assert_eq!(uni.get_cells()[uni.get_index(1, 2)], Cell::Dead);
+
+        uni.draw_rect(2, 2, u32::MAX, u32::MAX, false, false);
+        assert_eq!(uni.count_alive(), 14);
+    }
+
+    #[test]
+    fn it_should_draw_circle() {
+        let mut uni = Universe::new();
+        uni.set_width(7);
+        uni.set_height(7);
+        uni.draw_circle(3, 3, 2, true, false);
+        let live = uni.live_coordinates();
+        assert_eq!(live.len(), 12);
+        for &(row, col) in live.iter() {
+            assert!(live.contains(&(6 - row, col)));
+            assert!(live.contains(&(row, 6 - col)));
+            assert!(live.contains(&(col, row)));
+        }
+        assert!(live.contains(&(1, 3)) && live.contains(&(3, 5)));
+        assert_eq!(uni.get_cells()[uni.get_index(3, 3)], Cell::Dead);
+
+        uni.draw_circle(3, 3, 1, true, true);
+        assert_eq!(uni.count_alive(), 17);
+        uni.draw_circle(0, 0, 1, true, false);
+        assert_eq!(uni.get_cells()[uni.get_index(0, 1)], Cell::Alive);
+
+        let mut big = Universe::new();
+        big.set_width(64);
+        big.set_height(64);
+        big.draw_circle(31, 31, 30, true, false);
+        let live = big.live_coordinates();
+        assert_eq!(live.len(), 168);
+        assert!(live.contains(&(1, 31)) && live.contains(&(31, 61)) && live.contains(&(10, 10)));
+
+        uni.draw_circle(3, 3, u32::MAX, true, true);
+        assert_eq!(uni.count_alive(), 49);
+        uni.draw_circle(u32::MAX, 3, u32::MAX - 3, false, false);
+        assert_eq!(uni.count_alive(), 42);
+        assert!((0..7).all(|col| uni.get_cells()[uni.get_index(3, col)] == Cell::Dead));
+    }
+
+    #[test]
+    fn it_should_flood_fill_enclosed_region() {
+        let mut uni = Universe::new();
+        uni.set_width(7);
+        uni.set_height(7);
+        uni.draw_rect(1, 1, 5, 5, true, false);
+        uni.set_cells(&[(3, 3)]);
+        uni.flood_fill(2, 2, true);
+        assert_eq!(uni.count_alive(), 25);
+        assert_eq!(uni.get_cells()[uni.get_index(0, 0)], Cell::Dead);
+        assert_eq!(uni.get_cells()[uni.get_index(6, 3)], Cell::Dead);
+
+        uni.flood_fill(2, 2, true);
+        assert_eq!(uni.count_alive(), 25);
+        uni.flood_fill(0, 0, true);
+        assert_eq!(uni.count_alive(), 49);
+    }
+
+    #[test]
+    fn it_should_round_trip_url_fragment() {
+        let mut uni = get_init_universe();
+        uni.set_rule_checkerboard("B36/S23", "B36/S23");
+        uni.set_boundary(Boundary::Dead);
+        let fragment = uni.to_url_fragment();
+        assert!(fragment.starts_with("#B36/S23;d;"));
+
+        let copy = Universe::from_url_fragment(&fragment).ok().unwrap();
+        assert_eq!(copy.get_cells(), uni.get_cells());
+        assert_eq!((copy.width(), copy.height()), (5, 5));
+        assert_eq!(copy.rule_string(), "B36/S23");
+        assert_eq!(copy.boundary(), Boundary::Dead);
+        assert!(Universe::from_url_fragment(&fragment[1..]).is_ok());
+    }
+
+    #[test]
+    fn it_should_reject_malformed_url_fragment() {
+        let fragment = get_init_universe().to_url_fragment();
+        let state = fragment.rsplit(';').next().unwrap();
+        assert!(Universe::parse_url_fragment("#B3/S23;t").is_err());
+        assert!(Universe::parse_url_fragment(&format!("#B3/S2x;t;{}", state)).is_err());
+        assert!(Universe::parse_url_fragment(&format!("#B3/S23;x;{}", state)).is_err());
+        assert!(Universe::parse_url_fragment("#B3/S23;t;!!").is_err());
+        assert!(Universe::parse_url_fragment("#B3/S23;t;AAAA").is_err());
+        assert!(Universe::parse_url_fragment(&format!("#B3/S23;t;{}", state)).is_ok());
+    }
+
+    #[test]
+    fn it_should_morph_into_target() {
+        let mut target = Universe::new();
+        target.set_width(6);
+        target.set_height(6);
+        target.spawn_named("glider", 1, 1);
+        let mut uni = Universe::new();
+        uni.set_width(6);
+        uni.set_height(6);
+
+        assert!(!uni.morph_step(&target, 2));
+        assert_eq!(uni.count_alive(), 2);
+        assert!(!uni.morph_step(&target, 2));
+        assert_eq!(uni.count_alive(), 4);
+        assert!(uni.morph_step(&target, 2));
+        assert_eq!(uni.get_cells(), target.get_cells());
+        assert!(uni.morph_step(&target, 0));
+
+        let mut other = Universe::new();
+        assert!(!other.morph_step(&target, 100));
+        assert_eq!(other.width(), 64);
+    }
+
+    #[test]
+    fn it_should_get_population_profiles() {
+        let uni = get_init_universe();
+        assert_eq!(uni.col_profile(), vec![0, 3, 0, 0, 0]);
+        assert_eq!(uni.row_profile(), vec![0, 1, 1, 1, 0]);
+    }
+
+    #[test]
+    fn it_should_find_population_period() {
+        let mut uni = Universe::new();
+        uni.set_width(8);
+        uni.set_height(8);
+        uni.spawn_named("beacon", 2, 2);
+        let before = uni.get_cells().to_vec();
+        assert_eq!(uni.population_period(6), 2);
+        assert_eq!(uni.get_cells(), &before[..]);
+        assert_eq!(uni.population_period(3), 2);
+        assert_eq!(uni.population_period(2), 0);
+
+        assert_eq!(get_init_universe().population_period(4), 1);
+    }
+
+    #[test]
+    fn it_should_fire_pulser_on_its_phase() {
+        let mut uni = Universe::new();
+        uni.set_width(5);
+        uni.set_height(5);
+        uni.set_pulser(2, 2, 3, 4);
+        let mut fired = Vec::new();
+        for _ in 0..7 {
+            uni.tick();
+            if uni.count_alive() > 0 {
+                fired.push(uni.generation());
+            }
+        }
+        assert_eq!(fired, vec![1, 4, 7]);
+
+        uni.set_pulser(2, 2, 0, 0);
+        uni.tick_n(3);
+        assert_eq!(uni.count_alive(), 0);
+    }
+
+    #[test]
+    fn it_should_estimate_box_dimension() {
+        let mut uni = Universe::new();
+        uni.set_width(32);
+        uni.set_height(32);
+        assert_eq!(uni.box_dimension(), 0.0);
+        uni.draw_rect(3, 5, 16, 16, true, true);
+        assert!((uni.box_dimension() - 2.0).abs() < 1e-9);
+
+        uni.draw_rect(3, 5, 16, 16, false, true);
+        uni.draw_line(10, 0, 10, 31, true);
+        assert!((uni.box_dimension() - 1.0).abs() < 1e-9);
+
+        uni.set_cells(&[(1, 1)]);
+        uni.draw_line(10, 0, 10, 31, false);
+        assert_eq!(uni.box_dimension(), 0.0);
+    }
+
+    #[test]
+    fn it_should_apply_rule_to_hypothetical_cell() {
+        let uni = Universe::new();
+        for live_neighbors in 0..=8 {
+            let born = if live_neighbors == 3 {
+                Cell::Alive
+            } else {
+                Cell::Dead
+            };
+            assert_eq!(uni.next_state(Cell::Dead, live_neighbors), born);
+            let survives = if live_neighbors == 2 || live_neighbors == 3 {
+                Cell::Alive
+            } else {
+                Cell::Dead
+            };
+            assert_eq!(uni.next_state(Cell::Alive, live_neighbors), survives);
+        }
+    }
+
+    #[test]
+    fn it_should_sync_with_deltas() {
+        let mut uni = Universe::new();
+        uni.set_width(10);
+        uni.set_height(10);
+        uni.spawn_named("glider", 0, 0);
+        assert!(uni.delta_since_last_sync().is_empty());
+        let mut copy = Universe::from_bytes(&uni.full_sync()).unwrap();
+
+        uni.tick_n(3);
+        assert!(copy.apply_delta(&uni.delta_since_last_sync()));
+        assert_eq!(copy.get_cells(), uni.get_cells());
+        uni.tick();
+        uni.tick();
+        assert!(copy.apply_delta(&uni.delta_since_last_sync()));
+        assert_eq!(copy.get_cells(), uni.get_cells());
+        assert_eq!(uni.delta_since_last_sync(), vec![0, 0, 0, 0]);
+
+        assert!(!copy.apply_delta(&[]));
+        assert!(!copy.apply_delta(&[1, 0, 0, 0, 100, 0, 0, 0]));
+        assert!(!copy.apply_delta(&[1, 0, 0, 0, 5, 0]));
+        assert_eq!(copy.get_cells(), uni.get_cells());
+    }
+
+    #[test]
+    fn it_should_detect_wrap_use() {
+        let mut uni = Universe::new();
+        uni.set_width(8);
+        uni.set_height(8);
+        uni.spawn_named("blinker", 3, 3);
+        let before = uni.get_cells().to_vec();
+        assert!(!uni.uses_wrap(10));
+        assert_eq!(uni.get_cells(), &before[..]);
+
+        uni.set_width(8);
+        uni.spawn_named("glider", 3, 3);
+        assert!(!uni.uses_wrap(2));
+        assert!(uni.uses_wrap(10));
+        assert_eq!(uni.generation(), 0);
+        uni.set_boundary(Boundary::Dead);
+        assert!(!uni.uses_wrap(10));
+    }
+
+    #[test]
+    fn it_should_render_sprite_sheet() {
+        let mut wide = Universe::new();
+        wide.set_width(6);
+        wide.set_height(2);
+        wide.set_cells(&[(0, 5)]);
+        let boards = [get_init_universe(), wide, get_init_universe()];
+        let pixels = render_sheet_rgba(&boards, 2, 2, 1, 0xffff_ffff, 0x0000_00ff);
+
+        let (sheet_width, sheet_height) = (2 * 12 + 1, 2 * 10 + 1);
+        assert_eq!(pixels.len(), sheet_width * sheet_height * 4);
+        let pixel = |x: usize, y: usize| &pixels[(y * sheet_width + x) * 4..][..4];
+        assert_eq!(pixel(2, 2), &[0xff; 4]);
+        assert_eq!(pixel(0, 0), &[0, 0, 0, 0xff]);
+        assert_eq!(pixel(10, 0), &[0, 0, 0, 0]);
+        assert_eq!(pixel(12, 0), &[0, 0, 0, 0]);
+        assert_eq!(pixel(13 + 10, 0), &[0xff; 4]);
+        assert_eq!(pixel(13, 4), &[0, 0, 0, 0]);
+        assert_eq!(pixel(2, 11 + 2), &[0xff; 4]);
+        assert_eq!(pixel(13, 11), &[0, 0, 0, 0]);
+        assert!(render_sheet_rgba(&boards, 0, 2, 1, 0, 0).is_empty());
+        assert!(render_sheet_rgba(&boards, 2, 0, 1, 0, 0).is_empty());
+
+        let mut empty = Universe::new();
+        empty.set_width(0);
+        let boards = [empty, get_init_universe()];
+        let pixels = render_sheet_rgba(&boards, 2, 1, 0, 0xffff_ffff, 0x0000_00ff);
+        assert_eq!(pixels.len(), 10 * 64 * 4);
+    }
+
+    #[test]
+    fn it_should_find_stable_cells() {
+        let mut uni = Universe::new();
+        uni.set_width(9);
+        uni.set_height(5);
+        uni.spawn_named("block", 1, 1);
+        uni.spawn_named("blinker", 2, 5);
+        let before = uni.get_cells().to_vec();
+
+        let stable = uni.stable_cells(4);
+        assert_eq!(uni.get_cells(), &before[..]);
+        let live_stable: Vec<u32> = stable
+            .into_iter()
+            .filter(|&idx| before[idx as usize] == Cell::Alive)
+            .collect();
+        let expected = [(1, 1), (1, 2), (2, 1), (2, 2), (2, 6)];
+        assert_eq!(
+            live_stable,
+            expected
+                .iter()
+                .map(|&(row, col)| uni.get_index(row, col) as u32)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(uni.stable_cells(4).len(), 45 - 4);
+    }
+
+    #[test]
+    fn it_should_classify_symmetry() {
+        let uni = get_init_universe();
+        let expected =
+            Symmetry::Horizontal as u8 | Symmetry::Vertical as u8 | Symmetry::Rotate180 as u8;
+        assert_eq!(uni.symmetry_class(), expected);
+
+        let mut uni = Universe::new();
+        uni.set_width(8);
+        uni.set_height(8);
+        assert_eq!(uni.symmetry_class(), 0b11_1111);
+        uni.spawn_named("glider", 1, 1);
+        assert_eq!(uni.symmetry_class(), 0);
+        uni.draw_rect(1, 1, 3, 3, false, true);
+        uni.spawn_named("block", 5, 5);
+        assert_eq!(uni.symmetry_class(), 0b11_1111);
+    }
+
+    #[test]
+    fn it_should_evolve_until_symmetry() {
+        let mut uni = get_init_universe();
+        assert_eq!(uni.evolve_until_symmetry(Symmetry::Rotate180, 10), Some(0));
+        assert_eq!(uni.evolve_until_symmetry(Symmetry::Rotate90, 10), None);
+        assert_eq!(uni.generation(), 10);
+
+        let mut uni = Universe::new();
+        uni.set_width(10);
+        uni.set_height(10);
+        uni.set_cells(&[(3, 3), (3, 4), (4, 3)]);
+        assert_eq!(uni.evolve_until_symmetry(Symmetry::Diagonal, 4), Some(0));
+        assert_eq!(uni.evolve_until_symmetry(Symmetry::Rotate90, 4), Some(1));
+        assert_eq!(uni.count_alive(), 4);
+    }
+
+    #[test]
+    fn it_should_count_trajectory_length() {
+        let mut uni = get_init_universe();
+        assert_eq!(uni.trajectory_length(10), 2);
+        assert_eq!(uni.generation(), 0);
+
+        let mut uni = Universe::new();
+        uni.set_width(20);
+        uni.set_height(20);
+        uni.set_cells(&[(9, 9), (9, 10), (9, 11), (10, 10)]);
+        assert_eq!(uni.trajectory_length(20), 9 + 2);
+        assert_eq!(uni.trajectory_length(5), 6);
+        assert_eq!(uni.count_alive(), 4);
+    }
+
+    #[test]
+    fn it_should_render_smooth_thumbnail() {
+        let mut uni = Universe::new();
+        uni.set_width(8);
+        uni.set_height(8);
+        let checkerboard: Vec<(u32, u32)> = (0..8)
+            .flat_map(|row| (0..8).map(move |col| (row, col)))
+            .filter(|&(row, col)| (row + col) % 2 == 0)
+            .collect();
+        uni.set_cells(&checkerboard);
+
+        let pixels = uni.render_thumbnail_rgba(2, 2, 0xffff_ffff, 0x0000_00ff);
+        assert_eq!(pixels.len(), 2 * 2 * 4);
+        for pixel in pixels.chunks(4) {
+            assert_eq!(pixel, &[128, 128, 128, 255]);
+        }
+
+        let pixels = uni.render_thumbnail_rgba(8, 8, 0xff00_00ff, 0x0000_ffff);
+        assert_eq!(&pixels[..8], &[255, 0, 0, 255, 0, 0, 255, 255]);
+    }
+
+    #[test]
+    fn it_should_render_wrap_seam() {
+        let mut uni = get_init_universe();
+        let seam = 0xff00_00ff;
+        let pixels = uni.render_rgba_seam(2, 0xffff_ffff, 0x0000_00ff, seam);
+        assert_eq!(pixels.len(), 10 * 10 * 4);
+        let pixel = |pixels: &[u8], x: usize, y: usize| pixels[(y * 10 + x) * 4..][..4].to_vec();
+        for &(x, y) in [(0, 0), (9, 0), (0, 9), (9, 9), (5, 0)].iter() {
+            assert_eq!(pixel(&pixels, x, y), vec![0xff, 0, 0, 0xff]);
+        }
+        assert_eq!(pixel(&pixels, 1, 1), vec![0, 0, 0, 0xff]);
+        assert_eq!(pixel(&pixels, 2, 2), vec![0xff; 4]);
+
+        uni.set_boundary(Boundary::Dead);
+        let pixels = uni.render_rgba_seam(2, 0xffff_ffff, 0x0000_00ff, seam);
+        assert_eq!(pixel(&pixels, 0, 0), vec![0xff, 0, 0, 0x7f]);
+    }
+
+    #[test]
+    fn it_should_find_gun_emission_period() {
+        let mut uni = Universe::new();
+        uni.set_width(60);
+        uni.set_height(40);
+        uni.set_boundary(Boundary::Dead);
+        uni.spawn_named("gosper-glider-gun", 1, 1);
+        let before = uni.get_cells().to_vec();
+        assert_eq!(uni.emission_period(20, 33, 150), 30);
+        assert_eq!(uni.get_cells(), &before[..]);
+        assert_eq!(uni.emission_period(20, 33, 100), 0);
+        assert_eq!(uni.emission_period(38, 5, 150), 0);
+
+        assert_eq!(get_init_universe().emission_period(2, 2, 10), 2);
+    }
+
+    #[test]
+    fn it_should_follow_activity_centroid() {
+        let mut uni = Universe::new();
+        uni.set_width(20);
+        uni.set_height(20);
+        for &(row, col) in [(12, 12), (12, 16), (16, 12), (16, 16)].iter() {
+            uni.spawn_named("block", row, col);
+        }
+        uni.spawn_named("blinker", 2, 1);
+        uni.tick();
+        assert_eq!(uni.activity_centroid(), None);
+        uni.set_track_flips(true);
+        uni.tick_n(3);
+
+        let (row, col) = uni.activity_centroid().unwrap();
+        assert!((row - 2.0).abs() < 1e-9 && (col - 2.0).abs() < 1e-9);
+        uni.reset_flip_counts();
+        assert_eq!(uni.activity_centroid(), None);
+        uni.tick();
+        assert!(uni.activity_centroid().is_some());
+        uni.set_track_flips(false);
+        uni.tick();
+        assert_eq!(uni.activity_centroid(), None);
+    }
+
+    #[test]
+    fn it_should_iterate_over_generations() {
+        let mut uni = Universe::new();
+        uni.set_width(8);
+        uni.set_height(8);
+        uni.spawn_named("glider", 1, 1);
+        let mut manual = uni.clone();
+
+        let generations: Vec<Vec<Cell>> = uni.generations_iter().take(3).collect();
+        assert_eq!(generations.len(), 3);
+        for cells in generations.iter() {
+            manual.tick();
+            assert_eq!(manual.get_cells(), &cells[..]);
+        }
+        assert_eq!(uni.generation(), 3);
+        assert_eq!(uni.get_cells(), manual.get_cells());
+    }
+
+    #[test]
+    fn it_should_check_predecessor() {
+        let uni = get_init_universe();
+        let mut next = uni.clone();
+        next.tick();
+        assert!(uni.is_predecessor_of(&next));
+        assert!(!next.is_predecessor_of(&next));
+
+        let mut other = next.clone();
+        other.set_rule_checkerboard("B36/S23", "B36/S23");
+        assert!(!uni.is_predecessor_of(&other));
+
+        let mut rng = Rng::new(3);
+        let mut soup = Universe::new();
+        soup.set_width(16);
+        soup.set_height(16);
+        soup.fill_random(&mut rng, 0.4);
+        let mut unrelated = soup.clone();
+        unrelated.fill_random(&mut rng, 0.4);
+        assert!(!soup.is_predecessor_of(&unrelated));
+    }
+
+    #[test]
+    fn it_should_sample_live_cells() {
+        let mut rng = Rng::new(11);
+        let mut uni = Universe::new();
+        uni.set_width(16);
+        uni.set_height(16);
+        uni.fill_random(&mut rng, 0.3);
+
+        let sample = uni.sample_live(10, 42);
+        assert_eq!(sample.len(), 20);
+        let picked: HashSet<(u32, u32)> = sample.chunks(2).map(|p| (p[0], p[1])).collect();
+        assert_eq!(picked.len(), 10);
+        for &(row, col) in picked.iter() {
+            assert_eq!(uni.get_cells()[uni.get_index(row, col)], Cell::Alive);
+        }
+        assert_eq!(uni.sample_live(10, 42), sample);
+        assert_ne!(uni.sample_live(10, 43), sample);
+
+        let all = get_init_universe().sample_live(5, 42);
+        assert_eq!(all, vec![1, 1, 2, 1, 3, 1]);
+    }
+
+    #[test]
+    fn it_should_record_component_count_series() {
+        let mut uni = Universe::new();
+        uni.set_width(10);
+        uni.set_height(10);
+        uni.set_cells(&[(4, 4), (4, 5), (4, 6), (6, 5)]);
+        assert_eq!(uni.components().len(), 2);
+        let series = uni.component_count_series(3);
+        assert_eq!(series.len(), 3);
+        assert_eq!(series[0], 1);
+        assert_eq!(uni.generation(), 0);
+        assert_eq!(uni.components().len(), 2);
+    }
+
+    #[test]
+    fn it_should_name_allocator() {
+        let expected = if cfg!(feature = "wee_alloc") {
+            "wee_alloc"
+        } else {
+            "system"
+        };
+        assert_eq!(Universe::allocator_name(), expected);
+    }
+
+    #[test]
+    fn it_should_report_each_generation_while_running() {
+        let mut uni = get_init_universe();
+        let mut calls = Vec::new();
+        uni.run_with(3, |generation, population| {
+            calls.push((generation, population))
+        });
+        assert_eq!(calls, vec![(1, 3), (2, 3), (3, 3)]);
+        assert_eq!(uni.generation(), 3);
+    }
+
+    #[test]
+    fn it_should_find_safe_margin() {
+        let mut uni = Universe::new();
+        uni.set_width(8);
+        uni.set_height(8);
+        uni.spawn_named("glider", 0, 0);
+        assert_eq!(uni.safe_margin(40), 10);
+        assert_eq!(uni.safe_margin(0), 0);
+        assert_eq!(uni.width(), 8);
+
+        assert_eq!(get_init_universe().safe_margin(10), 1);
+        let mut uni = Universe::new();
+        uni.set_width(8);
+        uni.set_height(8);
+        uni.spawn_named("block", 5, 5);
+        assert_eq!(uni.safe_margin(10), 0);
+    }
+
+    #[test]
+    fn it_should_draw_lexicon_art() {
+        let mut uni = Universe::new();
+        uni.set_width(8);
+        uni.set_height(8);
+        assert_eq!(uni.to_lexicon_art(), "");
+        uni.spawn_named("glider", 2, 3);
+        assert_eq!(uni.to_lexicon_art(), ".*.\n..*\n***\n");
+    }
+
+    #[test]
+    fn it_should_detect_orphan_neighborhood() {
+        let mut uni = get_init_universe();
+        assert!(!uni.has_orphan_neighborhood());
+        uni.draw_rect(1, 1, 3, 3, true, true);
+        assert!(!uni.has_orphan_neighborhood());
+
+        uni.set_rule_checkerboard("B/S23", "B/S23");
+        assert!(uni.has_orphan_neighborhood());
+        assert!(uni.has_orphan_neighborhood());
+        assert_eq!(
+            uni.reachable_windows.as_ref().map(|&(rules, _)| rules),
+            Some(uni.rules)
+        );
+        let mut blinker = get_init_universe();
+        blinker.set_rule_checkerboard("B/S23", "B/S23");
+        assert!(!blinker.has_orphan_neighborhood());
+    }
+
+    #[test]
+    fn it_should_compute_next_states_for_rules() {
+        let mut rng = Rng::new(5);
+        let mut uni = Universe::new();
+        uni.set_width(12);
+        uni.set_height(12);
+        uni.fill_random(&mut rng, 0.4);
+        let rules = vec!["B3/S23".to_string(), "B36/S23".to_string()];
+        let states = uni.next_states_for_rules(&rules).ok().unwrap();
+        assert_eq!(states.len(), 2 * 144);
+
+        let mut life = uni.clone();
+        life.tick();
+        let mut highlife = uni.clone();
+        highlife.set_rule_checkerboard("B36/S23", "B36/S23");
+        highlife.tick();
+        let as_bytes =
+            |uni: &Universe| uni.get_cells().iter().map(|&c| c as u8).collect::<Vec<_>>();
+        assert_eq!(&states[..144], &as_bytes(&life)[..]);
+        assert_eq!(&states[144..], &as_bytes(&highlife)[..]);
+        assert_eq!(uni.generation(), 0);
+    }
+
+    #[test]
+    fn it_should_measure_compressibility() {
+        let mut uni = Universe::new();
+        uni.draw_rect(0, 0, 64, 64, true, true);
+        assert!(uni.compressibility() < 0.1);
+
+        let mut rng = Rng::new(9);
+        uni.fill_random(&mut rng, 0.5);
+        assert!(uni.compressibility() > 1.0);
+    }
+
+    #[test]
+    fn it_should_kill_cells_by_age() {
+        let mut uni = Universe::new();
+        uni.set_width(10);
+        uni.set_height(6);
+        uni.spawn_named("block", 1, 1);
+        uni.spawn_named("blinker", 2, 5);
+        uni.tick_n(2);
+        assert_eq!(uni.age(1, 1), 0);
+        uni.set_track_ages(true);
+        uni.tick_n(4);
+        assert_eq!(uni.age(1, 1), 4);
+        assert_eq!(uni.age(2, 6), 4);
+        assert_eq!(uni.age(2, 5), 0);
+        assert_eq!(uni.age(0, 0), 0);
+
+        let mut young = uni.clone();
+        uni.kill_older_than(2);
+        assert_eq!(uni.live_coordinates(), vec![(2, 5), (2, 7)]);
+        young.kill_younger_than(1);
+        assert_eq!(young.count_alive(), 5);
+        assert_eq!(young.get_cells()[young.get_index(2, 5)], Cell::Dead);
+
+        young.toggle_cell(1, 1);
+        young.toggle_cell(1, 1);
+        young.draw_rect(1, 2, 1, 1, false, true);
+        young.set_cells(&[(1, 2)]);
+        young.tick();
+        assert_eq!(young.age(1, 1), 1);
+        assert_eq!(young.age(1, 2), 1);
+        assert_eq!(young.age(2, 1), 5);
+        young.set_track_ages(false);
+        young.tick();
+        assert_eq!(young.age(2, 1), 0);
+    }
+
+    #[test]
+    fn it_should_restart_ages_of_cells_set_by_edits() {
+        let mut aged = Universe::new();
+        aged.set_width(6);
+        aged.set_height(6);
+        aged.spawn_named("block", 1, 1);
+        aged.set_track_ages(true);
+        aged.tick_n(5);
+        assert_eq!(aged.age(1, 1), 5);
+
+        let mut uni = aged.clone();
+        uni.flood_fill(1, 1, false);
+        uni.flood_fill(1, 1, true);
+        assert_eq!(uni.age(1, 1), 0);
+
+        let mut uni = aged.clone();
+        let mut empty = Universe::new();
+        empty.set_width(6);
+        empty.set_height(6);
+        assert!(uni.morph_step(&empty, 4));
+        assert!(uni.morph_step(&aged, 4));
+        assert_eq!(uni.age(1, 1), 0);
+
+        let mut uni = aged.clone();
+        uni.apply_gravity(Direction::Up);
+        assert_eq!(uni.age(1, 1), 0);
+
+        let mut uni = aged.clone();
+        uni.smooth();
+        assert!(uni.ages.iter().all(|&age| age == 0));
+
+        let mut uni = aged.clone();
+        uni.tick_staged(0, 2);
+        uni.tick_staged(1, 2);
+        assert_eq!(uni.age(1, 1), 0);
+    }
+
+    #[test]
+    fn it_should_measure_component_distances() {
+        let mut uni = Universe::new();
+        uni.set_width(12);
+        uni.set_height(6);
+        uni.spawn_named("block", 1, 1);
+        uni.spawn_named("block", 1, 6);
+        assert_eq!(uni.component_distances(), vec![4.0]);
+
+        uni.spawn_named("block", 4, 10);
+        let distances = uni.component_distances();
+        assert_eq!(distances.len(), 3);
+        assert!((distances[0] - 4.0).abs() < 1e-6);
+        assert!((distances[1] - 8.0f32.sqrt()).abs() < 1e-6);
+        uni.set_boundary(Boundary::Dead);
+        assert!((uni.component_distances()[1] - 8.0f32.hypot(2.0)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn it_should_apply_gravity() {
+        let mut uni = Universe::new();
+        uni.set_width(4);
+        uni.set_height(6);
+        uni.set_cells(&[(0, 1), (2, 1), (3, 1), (1, 3)]);
+        uni.apply_gravity(Direction::Down);
+        assert_eq!(uni.live_coordinates(), vec![(3, 1), (4, 1), (5, 1), (5, 3)]);
+
+        uni.apply_gravity(Direction::Left);
+        assert_eq!(uni.live_coordinates(), vec![(3, 0), (4, 0), (5, 0), (5, 1)]);
+        uni.apply_gravity(Direction::Up);
+        assert_eq!(uni.live_coordinates(), vec![(0, 0), (0, 1), (1, 0), (2, 0)]);
+        uni.apply_gravity(Direction::Right);
+        assert_eq!(uni.live_coordinates(), vec![(0, 2), (0, 3), (1, 3), (2, 3)]);
+    }
+
+    #[test]
+    fn it_should_score_methuselah_above_dying_board() {
+        let mut uni = Universe::new();
+        uni.set_width(32);
+        uni.set_height(32);
+        uni.set_cells(&[(10, 10), (20, 20)]);
+        let dying = uni.interest_score(200);
+        assert_eq!(dying, 2.0);
+
+        uni.set_width(32);
+        uni.spawn_named("r-pentomino", 14, 14);
+        let methuselah = uni.interest_score(200);
+        assert!(methuselah > 100.0);
+        assert_eq!(uni.count_alive(), 5);
+    }
+
+    #[test]
+    fn it_should_borrow_cells() {
+        let uni = get_init_universe();
+        let cells = uni.cells_cow();
+        assert!(match cells {
+            Cow::Borrowed(_) => true,
+            Cow::Owned(_) => false,
+        });
+        assert_eq!(&cells[..], uni.get_cells());
+    }
+
+    #[test]
+    fn it_should_record_bounding_box_series() {
+        let mut uni = Universe::new();
+        uni.set_width(16);
+        uni.set_height(16);
+        uni.spawn_named("glider", 1, 1);
+        let series = uni.bounding_box_series(8);
+        assert_eq!(series.len(), 32);
+        assert_eq!(&series[12..16], &[2, 2, 4, 4]);
+        assert_eq!(&series[28..32], &[3, 3, 5, 5]);
+        assert_eq!(uni.generation(), 0);
+
+        uni.set_width(16);
+        uni.set_cells(&[(5, 5)]);
+        assert_eq!(uni.bounding_box_series(1), vec![u32::MAX; 4]);
+    }
+
+    #[test]
+    fn it_should_smooth_by_majority() {
+        let mut uni = Universe::new();
+        uni.set_width(10);
+        uni.set_height(10);
+        uni.draw_rect(4, 4, 6, 6, true, true);
+        uni.set_cells(&[(1, 1)]);
+        uni.smooth();
+        assert_eq!(uni.get_cells()[uni.get_index(1, 1)], Cell::Dead);
+        assert_eq!(uni.get_cells()[uni.get_index(6, 6)], Cell::Alive);
+        assert_eq!(uni.get_cells()[uni.get_index(4, 4)], Cell::Dead);
+        assert_eq!(uni.count_alive(), 36 - 4);
+
+        uni.set_boundary(Boundary::Dead);
+        uni.draw_rect(4, 4, 6, 6, true, true);
+        uni.smooth();
+        assert_eq!(uni.get_cells()[uni.get_index(9, 9)], Cell::Dead);
+        assert_eq!(uni.get_cells()[uni.get_index(9, 8)], Cell::Alive);
+    }
+
+    #[test]
+    fn it_should_count_generations_until_population() {
+        let mut uni = Universe::new();
+        uni.set_width(10);
+        uni.set_height(10);
+        uni.draw_line(2, 2, 6, 6, true);
+        assert_eq!(uni.generations_until_population(1, 10), Some(2));
+        assert_eq!(uni.generations_until_population(2, 10), Some(2));
+        assert_eq!(uni.generations_until_population(4, 10), Some(1));
+        assert_eq!(uni.generations_until_population(5, 10), Some(0));
+        assert_eq!(uni.generations_until_population(0, 2), None);
+        assert_eq!(uni.generations_until_population(9, 10), None);
+        assert_eq!(uni.count_alive(), 5);
+    }
+
+    #[test]
+    fn it_should_render_gridlines() {
+        let uni = get_init_universe();
+        let pixels = uni.render_with_gridlines(3, 0xffff_ffff, 0x0000_00ff, 0x00ff_00ff, 2);
+        assert_eq!(pixels.len(), 15 * 15 * 4);
+        let pixel = |x: usize, y: usize| pixels[(y * 15 + x) * 4..][..4].to_vec();
+        let line = vec![0, 0xff, 0, 0xff];
+        for &(x, y) in [(0, 0), (0, 7), (6, 1), (14, 6), (12, 12), (5, 12)].iter() {
+            assert_eq!(pixel(x, y), line);
+        }
+        assert_eq!(pixel(3, 3), vec![0xff; 4]);
+        assert_eq!(pixel(4, 4), vec![0xff; 4]);
+        assert_eq!(pixel(9, 10), vec![0, 0, 0, 0xff]);
+
+        let plain = uni.render_with_gridlines(3, 0xffff_ffff, 0x0000_00ff, 0x00ff_00ff, 0);
+        assert_eq!(plain, uni.render_rgba(3, 0xffff_ffff, 0x0000_00ff));
+    }
+
+    #[test]
+    fn it_should_color_states_by_hash() {
+        let uni = get_init_universe();
+        let same = get_init_universe();
+        assert_eq!(uni.state_hash(), same.state_hash());
+        assert_eq!(uni.state_color(), same.state_color());
+        assert_eq!(uni.state_color() & 0xff, 0xff);
+
+        let mut changed = get_init_universe();
+        changed.toggle_cell(0, 0);
+        assert_ne!(uni.state_hash(), changed.state_hash());
+        assert_ne!(uni.state_color(), changed.state_color());
+    }
+
+    #[test]
+    fn it_should_follow_rule_schedule() {
+        let mut uni = get_init_universe();
+        uni.set_rule_schedule("3:B36/S23, 0:B3/S23");
+        assert_eq!(uni.rule_string(), "B3/S23");
+        uni.tick_n(2);
+        assert_eq!(uni.rule_string(), "B3/S23");
+        uni.tick();
+        assert_eq!(uni.rule_string(), "B36/S23");
+
+        uni.set_rule_schedule("1:B3/S2x");
+        assert_eq!(uni.rule_string(), "B36/S23");
+        uni.set_rule_schedule("");
+        uni.tick();
+        assert_eq!(uni.rule_string(), "B36/S23");
+        uni.set_rule_schedule("4:B3/S23,10:B2/S");
+        assert_eq!(uni.rule_string(), "B3/S23");
+    }
+
+    #[test]
+    fn it_should_drop_rule_schedule_for_rules_set_by_hand() {
+        let mut uni = get_init_universe();
+        uni.set_rule_schedule("5:B36/S23");
+        uni.set_rule_checkerboard("B2/S", "B2/S");
+        uni.tick();
+        assert_eq!(uni.rule_string(), "B2/S");
+
+        uni.set_rule_schedule("0:B36/S23");
+        assert!(uni.store_rule_preset(0, "B2/S"));
+        assert!(uni.apply_rule_preset(0));
+        uni.tick();
+        assert_eq!(uni.rule_string(), "B2/S");
+
+        uni.set_rule_schedule("9:B36/S23");
+        uni.open_pattern("x = 1, y = 1, rule = B2/S\no!", 2)
+            .ok()
+            .unwrap();
+        uni.tick();
+        assert_eq!(uni.rule_string(), "B2/S");
+    }
+
+    #[test]
+    fn it_should_follow_rule_schedule_backwards() {
+        let mut uni = get_init_universe();
+        uni.set_update_mode(UpdateMode::SecondOrder);
+        uni.set_rule_schedule("2:B36/S23,4:B2/S");
+        let start = uni.get_cells().to_vec();
+        uni.step_by(5).ok().unwrap();
+        assert_eq!(uni.rule_string(), "B2/S");
+        uni.step_by(-2).ok().unwrap();
+        assert_eq!(uni.rule_string(), "B36/S23");
+        uni.step_by(-2).ok().unwrap();
+        assert_eq!(uni.rule_string(), "B3/S23");
+        uni.step_by(-1).ok().unwrap();
+        assert_eq!(uni.get_cells(), &start[..]);
+    }
+
+    #[test]
+    fn it_should_find_components_within_distance() {
+        let mut uni = Universe::new();
+        uni.set_width(16);
+        uni.set_height(6);
+        uni.spawn_named("block", 1, 1);
+        uni.spawn_named("block", 1, 7);
+        assert_eq!(uni.components_within(4), vec![]);
+        assert_eq!(uni.components_within(5), vec![(0, 1)]);
+
+        uni.spawn_named("block", 3, 12);
+        assert_eq!(uni.components_within(5), vec![(0, 1), (0, 2), (1, 2)]);
+        uni.set_boundary(Boundary::Dead);
+        assert_eq!(uni.components_within(5), vec![(0, 1), (1, 2)]);
+    }
+
+    #[test]
+    fn it_should_identify_patterns_by_shape() {
+        let mut glider = Universe::new();
+        glider.set_width(10);
+        glider.set_height(10);
+        glider.spawn_named("glider", 1, 1);
+        let mut rotated = Universe::new();
+        rotated.set_width(12);
+        rotated.set_height(12);
+        rotated.set_cells(&[(5, 5), (6, 5), (6, 7), (7, 5), (7, 6)]);
+        let mut block = Universe::new();
+        block.set_width(10);
+        block.set_height(10);
+        block.spawn_named("block", 1, 1);
+
+        let id = glider.pattern_id();
+        assert_eq!(id.len(), 13);
+        assert_eq!(rotated.pattern_id(), id);
+        assert_ne!(block.pattern_id(), id);
+    }
+
+    #[test]
+    fn it_should_tick_in_stages() {
+        let mut uni = Universe::new();
+        uni.set_width(10);
+        uni.set_height(10);
+        uni.spawn_named("glider", 2, 2);
+        uni.spawn_named("beacon", 5, 5);
+        let mut expected = uni.clone();
+        for _ in 0..3 {
+            expected.tick();
+            for stage in 0..4 {
+                uni.tick_staged(stage, 4);
+                assert_eq!(uni.get_cells() == expected.get_cells(), stage == 3);
+            }
+        }
+        uni.tick_staged(4, 4);
+        assert_eq!(uni.get_cells(), expected.get_cells());
+    }
+
+    #[test]
+    fn it_should_compute_laplacian() {
+        let mut uni = Universe::new();
+        uni.set_width(8);
+        uni.set_height(8);
+        uni.draw_rect(2, 2, 4, 4, true, true);
+        let laplacian = uni.laplacian();
+        let at = |row, col| laplacian[uni.get_index(row, col)];
+        assert_eq!(at(3, 3), 0);
+        assert_eq!(at(4, 4), 0);
+        assert_eq!(at(2, 3), -1);
+        assert_eq!(at(2, 2), -2);
+        assert_eq!(at(1, 3), 1);
+        assert_eq!(at(1, 1), 0);
+        assert_eq!(laplacian.iter().map(|&v| i32::from(v)).sum::<i32>(), 0);
+
+        uni.set_width(8);
+        uni.set_boundary(Boundary::Dead);
+        uni.set_cells(&[(0, 0)]);
+        assert_eq!(uni.laplacian()[0], -4);
+        assert_eq!(uni.laplacian()[7], 0);
+    }
+
+    #[test]
+    fn it_should_step_both_ways_under_second_order() {
+        let mut uni = get_init_universe();
+        uni.set_cells(&[(0, 3), (1, 3), (4, 4)]);
+        uni.set_second_order(true);
+        assert_eq!(uni.update_mode(), UpdateMode::SecondOrder);
+        let start = uni.get_cells().to_vec();
+
+        uni.step_by(5).ok().unwrap();
+        assert_eq!(uni.generation(), 5);
+        assert_ne!(uni.get_cells(), &start[..]);
+        uni.step_by(-5).ok().unwrap();
+        assert_eq!(uni.generation(), 0);
+        assert_eq!(uni.get_cells(), &start[..]);
+
+        uni.step_by(3).ok().unwrap();
+        let third = uni.get_cells().to_vec();
+        uni.step_by(-1).ok().unwrap();
+        assert_eq!(uni.generation(), 2);
+        uni.step_by(1).ok().unwrap();
+        assert_eq!(uni.generation(), 3);
+        assert_eq!(uni.get_cells(), &third[..]);
+
+        let mut restored = Universe::from_bytes(&uni.to_bytes()).unwrap();
+        restored.tick_n(2);
+        uni.tick_n(2);
+        assert_eq!(restored.get_cells(), uni.get_cells());
+
+        uni.set_second_order(false);
+        assert_eq!(uni.update_mode(), UpdateMode::Synchronous);
+        uni.set_update_mode(UpdateMode::RandomAsync { seed: 3 });
+        uni.set_second_order(false);
+        assert_eq!(uni.update_mode(), UpdateMode::RandomAsync { seed: 3 });
+    }
+
+    #[test]
+    fn it_should_render_braille() {
+        let mut uni = Universe::new();
+        uni.set_width(3);
+        uni.set_height(5);
+        uni.draw_rect(0, 0, 4, 2, true, true);
+        assert_eq!(uni.render_braille(), "\u{28FF}\u{2800}\n\u{2800}\u{2800}\n");
+
+        uni.set_width(3);
+        uni.set_cells(&[(0, 0), (3, 1), (4, 2)]);
+        assert_eq!(uni.render_braille(), "\u{2881}\u{2800}\n\u{2800}\u{2801}\n");
+    }
+
+    #[test]
+    fn it_should_classify_outcomes() {
+        let uni = get_init_universe();
+        let outcomes = uni.outcome_grid();
+        let at = |row, col| outcomes[uni.get_index(row, col)];
+        assert_eq!(at(2, 0), 1);
+        assert_eq!(at(2, 2), 1);
+        assert_eq!(at(2, 1), 2);
+        assert_eq!(at(1, 1), 3);
+        assert_eq!(at(3, 1), 3);
+        assert_eq!(outcomes.iter().filter(|&&code| code == 0).count(), 20);
+    }
+
+    #[test]
+    fn it_should_clip_regions_when_resized() {
+        let mut uni = Universe::new();
+        uni.set_wrap_region(0, 0, 64, 64);
+        uni.set_play_area(8, 8, 48, 48);
+        uni.set_width(16);
+        uni.set_height(16);
+        assert_eq!(uni.wrap_region, Some((0, 0, 16, 16)));
+        assert_eq!(uni.play_area, Some((8, 8, 8, 8)));
+        uni.set_cells(&[(9, 9), (9, 10), (10, 9), (10, 10)]);
+        uni.tick();
+        assert_eq!(uni.count_alive(), 4);
+
+        uni.set_wrap_region(10, 10, 6, 6);
+        uni.set_width(8);
+        assert_eq!(uni.wrap_region, None);
+        uni.tick();
+    }
+
+    #[test]
+    fn it_should_keep_pulsers_on_the_universe() {
+        let mut uni = Universe::new();
+        uni.set_pulser(100, 100, 1, 0);
+        assert!(uni.pulsers.is_empty());
+
+        uni.set_pulser(40, 40, 1, 0);
+        uni.set_width(16);
+        uni.set_height(16);
+        assert!(uni.pulsers.is_empty());
+        uni.tick();
+
+        uni.set_pulser(2, 2, 1, 0);
+        uni.grow(1, 0, 0, 2);
+        uni.tick();
+        assert_eq!(uni.live_coordinates(), vec![(3, 4)]);
     }
 }
@@ -0,0 +1,129 @@
+//! Helpers for working with patterns as sets of `(row, column)` points,
+//! independent of where they sit on a board.
+
+/// Get the canonical form of a pattern: the lexicographically smallest of
+/// its eight rotations and reflections, translated so its bounding box
+/// starts at the origin.
+///
+/// Two patterns are the same shape, regardless of position or orientation,
+/// exactly when their canonical forms are equal.
+pub fn canonical(points: &[(i64, i64)]) -> Vec<(i64, i64)> {
+    (0..8)
+        .map(|orientation| normalize(points.iter().map(|&p| transform(p, orientation))))
+        .min()
+        .unwrap_or_default()
+}
+
+/// Apply one of the eight symmetries of the square to a point.
+pub fn transform((row, col): (i64, i64), orientation: u8) -> (i64, i64) {
+    match orientation {
+        0 => (row, col),
+        1 => (col, -row),
+        2 => (-row, -col),
+        3 => (-col, row),
+        4 => (row, -col),
+        5 => (-row, col),
+        6 => (col, row),
+        _ => (-col, -row),
+    }
+}
+
+/// Translate points so the bounding box starts at the origin, and sort them.
+pub fn normalize<I: Iterator<Item = (i64, i64)>>(points: I) -> Vec<(i64, i64)> {
+    let mut points: Vec<(i64, i64)> = points.collect();
+    let min_row = points.iter().map(|p| p.0).min().unwrap_or(0);
+    let min_col = points.iter().map(|p| p.1).min().unwrap_or(0);
+    for p in points.iter_mut() {
+        *p = (p.0 - min_row, p.1 - min_col);
+    }
+    points.sort();
+    points
+}
+
+/// The four phases of the glider, heading down and to the right.
+const GLIDER_PHASES: [[(i64, i64); 5]; 4] = [
+    [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)],
+    [(0, 0), (0, 2), (1, 1), (1, 2), (2, 1)],
+    [(0, 2), (1, 0), (1, 2), (2, 1), (2, 2)],
+    [(0, 0), (1, 1), (1, 2), (2, 0), (2, 1)],
+];
+
+/// Get every phase of the glider in every orientation, each normalized to
+/// a 3×3 bounding box at the origin.
+pub fn glider_templates() -> Vec<Vec<(i64, i64)>> {
+    let mut templates: Vec<Vec<(i64, i64)>> = GLIDER_PHASES
+        .iter()
+        .flat_map(|phase| {
+            (0..8)
+                .map(move |orientation| normalize(phase.iter().map(|&p| transform(p, orientation))))
+        })
+        .collect();
+    templates.sort();
+    templates.dedup();
+    templates
+}
+
+/// Get the live cells of a well-known pattern by name, relative to its
+/// top-left corner.
+pub fn named(name: &str) -> Option<&'static [(u32, u32)]> {
+    let cells: &'static [(u32, u32)] = match name {
+        "block" => &[(0, 0), (0, 1), (1, 0), (1, 1)],
+        "beehive" => &[(0, 1), (0, 2), (1, 0), (1, 3), (2, 1), (2, 2)],
+        "blinker" => &[(0, 0), (0, 1), (0, 2)],
+        "toad" => &[(0, 1), (0, 2), (0, 3), (1, 0), (1, 1), (1, 2)],
+        "beacon" => &[(0, 0), (0, 1), (1, 0), (2, 3), (3, 2), (3, 3)],
+        "glider" => &[(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)],
+        "lwss" => &[
+            (0, 1),
+            (0, 4),
+            (1, 0),
+            (2, 0),
+            (2, 4),
+            (3, 0),
+            (3, 1),
+            (3, 2),
+            (3, 3),
+        ],
+        "r-pentomino" => &[(0, 1), (0, 2), (1, 0), (1, 1), (2, 1)],
+        "gosper-glider-gun" => &[
+            (0, 24),
+            (1, 22),
+            (1, 24),
+            (2, 12),
+            (2, 13),
+            (2, 20),
+            (2, 21),
+            (2, 34),
+            (2, 35),
+            (3, 11),
+            (3, 15),
+            (3, 20),
+            (3, 21),
+            (3, 34),
+            (3, 35),
+            (4, 0),
+            (4, 1),
+            (4, 10),
+            (4, 16),
+            (4, 20),
+            (4, 21),
+            (5, 0),
+            (5, 1),
+            (5, 10),
+            (5, 14),
+            (5, 16),
+            (5, 17),
+            (5, 22),
+            (5, 24),
+            (6, 10),
+            (6, 16),
+            (6, 24),
+            (7, 11),
+            (7, 15),
+            (8, 12),
+            (8, 13),
+        ],
+        _ => return None,
+    };
+    Some(cells)
+}
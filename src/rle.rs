@@ -0,0 +1,132 @@
+//! Parsing of patterns in the RLE format used by Golly and the LifeWiki.
+
+use rule::Rule;
+
+const TOO_LARGE: &str = "invalid RLE: pattern is too large";
+
+/// A pattern read from RLE.
+#[derive(Debug, PartialEq)]
+pub struct Pattern {
+    pub width: u32,
+    pub height: u32,
+    /// `(row, column)` of each live cell.
+    pub cells: Vec<(u32, u32)>,
+    /// The rule from the header, if it names one.
+    pub rule: Option<Rule>,
+}
+
+/// Parse a pattern such as `x = 3, y = 3, rule = B3/S23\nbo$2bo$3o!`.
+///
+/// Lines starting with `#` are comments. `b` or `.` is a dead cell, any
+/// other letter a live cell, `$` ends a row and `!` ends the pattern; each
+/// may be preceded by a repeat count. The size is taken from the header,
+/// grown if the cells don't fit.
+pub fn parse(rle: &str) -> Result<Pattern, String> {
+    let mut pattern = Pattern {
+        width: 0,
+        height: 0,
+        cells: Vec::new(),
+        rule: None,
+    };
+    let (mut row, mut col) = (0u32, 0u32);
+    let mut count: Option<u32> = None;
+    let mut seen_header = false;
+    'lines: for line in rle.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if !seen_header && line.starts_with('x') {
+            seen_header = true;
+            parse_header(line, &mut pattern)?;
+            continue;
+        }
+        for c in line.chars() {
+            if let Some(digit) = c.to_digit(10) {
+                let n = count.unwrap_or(0);
+                count = Some(
+                    n.checked_mul(10)
+                        .and_then(|n| n.checked_add(digit))
+                        .ok_or("invalid RLE: repeat count is too large")?,
+                );
+                continue;
+            }
+            let n = count.take().unwrap_or(1);
+            match c {
+                'b' | '.' => col = col.checked_add(n).ok_or(TOO_LARGE)?,
+                '$' => {
+                    row = row.checked_add(n).ok_or(TOO_LARGE)?;
+                    col = 0;
+                }
+                '!' => break 'lines,
+                c if c.is_ascii_alphabetic() => {
+                    let end = col.checked_add(n).ok_or(TOO_LARGE)?;
+                    pattern.cells.extend((col..end).map(|col| (row, col)));
+                    col = end;
+                }
+                c if c.is_whitespace() => {}
+                c => return Err(format!("invalid RLE: unexpected `{}`", c)),
+            }
+            pattern.width = pattern.width.max(col);
+        }
+    }
+    for &(row, col) in &pattern.cells {
+        pattern.width = pattern.width.max(col + 1);
+        pattern.height = pattern.height.max(row.checked_add(1).ok_or(TOO_LARGE)?);
+    }
+    Ok(pattern)
+}
+
+fn parse_header(line: &str, pattern: &mut Pattern) -> Result<(), String> {
+    for field in line.split(',') {
+        let mut parts = field.splitn(2, '=');
+        let key = parts.next().unwrap_or("").trim();
+        let value = parts
+            .next()
+            .map(str::trim)
+            .ok_or_else(|| format!("invalid RLE header `{}`", line))?;
+        match key {
+            "x" => {
+                pattern.width = value
+                    .parse()
+                    .map_err(|_| format!("invalid width `{}`", value))?
+            }
+            "y" => {
+                pattern.height = value
+                    .parse()
+                    .map_err(|_| format!("invalid height `{}`", value))?
+            }
+            "rule" => pattern.rule = Some(Rule::parse(value)?),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_parse_rle() {
+        let glider = parse("#N Glider\nx = 3, y = 3, rule = B3/S23\nbob$2bo$3o!").unwrap();
+        assert_eq!(glider.width, 3);
+        assert_eq!(glider.height, 3);
+        assert_eq!(glider.cells, vec![(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)]);
+        assert_eq!(glider.rule, Some(Rule::life()));
+
+        let headerless = parse("2o$\n2o!").unwrap();
+        assert_eq!((headerless.width, headerless.height), (2, 2));
+        assert_eq!(headerless.rule, None);
+    }
+
+    #[test]
+    fn it_should_reject_bad_rle() {
+        assert!(parse("x = 3, y = three\n3o!").is_err());
+        assert!(parse("x = 3, y = 1, rule = B9/S\n3o!").is_err());
+        assert!(parse("3o%!").is_err());
+        assert!(parse("4294967295b2o!").is_err());
+        assert!(parse("4294967295$o!").is_err());
+        assert!(parse("4294967294$o!").is_ok());
+    }
+}
@@ -0,0 +1,193 @@
+use std::fmt;
+
+use Cell;
+
+/// A Life-like rule, written in B/S notation such as `B3/S23`.
+///
+/// Bit `n` of `birth` is set when a dead cell with `n` live neighbors comes
+/// alive, and bit `n` of `survival` when a live cell with `n` live neighbors
+/// stays alive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rule {
+    birth: u16,
+    survival: u16,
+}
+
+impl Rule {
+    /// Conway's Game of Life, `B3/S23`.
+    pub fn life() -> Rule {
+        Rule {
+            birth: 1 << 3,
+            survival: (1 << 2) | (1 << 3),
+        }
+    }
+
+    /// Parse a rule in B/S notation, e.g. `B3/S23` or `b36/s23`.
+    pub fn parse(rulestring: &str) -> Result<Rule, String> {
+        let mut birth = None;
+        let mut survival = None;
+        for part in rulestring.trim().split('/') {
+            let mut chars = part.trim().chars();
+            let target = match chars.next() {
+                Some('B') | Some('b') => &mut birth,
+                Some('S') | Some('s') => &mut survival,
+                _ => {
+                    return Err(format!(
+                        "invalid rule `{}`: expected B/S notation",
+                        rulestring
+                    ))
+                }
+            };
+            if target.is_some() {
+                return Err(format!("invalid rule `{}`: repeated section", rulestring));
+            }
+            let mut bits = 0u16;
+            for c in chars {
+                match c.to_digit(10) {
+                    Some(n) if n <= 8 => bits |= 1 << n,
+                    _ => {
+                        return Err(format!(
+                            "invalid rule `{}`: `{}` is not a neighbor count",
+                            rulestring, c
+                        ))
+                    }
+                }
+            }
+            *target = Some(bits);
+        }
+        match (birth, survival) {
+            (Some(birth), Some(survival)) => Ok(Rule { birth, survival }),
+            _ => Err(format!(
+                "invalid rule `{}`: expected B/S notation",
+                rulestring
+            )),
+        }
+    }
+
+    /// Get the next state of a cell with `live_neighbors` live neighbors.
+    pub fn next(self, cell: Cell, live_neighbors: u8) -> Cell {
+        let bits = match cell {
+            Cell::Alive => self.survival,
+            Cell::Dead => self.birth,
+        };
+        if live_neighbors <= 8 && bits & (1 << live_neighbors) != 0 {
+            Cell::Alive
+        } else {
+            Cell::Dead
+        }
+    }
+}
+
+/// Find which 3x3 windows of cells can be the result of a tick.
+///
+/// `rules[0]` applies to the top-left cell of the window and those an even
+/// number of steps from it, `rules[1]` to the rest. Bit `3 * row + col` of an
+/// index stands for the cell at `(row, col)` of the window being alive, and
+/// the entry at that index is whether some 5x5 arrangement of cells turns
+/// into that window.
+///
+/// Rather than trying all 2^25 arrangements, this builds them up one row at a
+/// time, keeping only the last two rows and the window rows produced so far.
+pub fn reachable_windows(rules: [Rule; 2]) -> Vec<bool> {
+    // Each state is `prefix << 10 | middle << 5 | above`, where `above` and
+    // `middle` are the last two 5-cell input rows.
+    let mut states = vec![true; 1 << 10];
+    for out_row in 0..3 {
+        let mut next = vec![false; 1 << (10 + 3 * (out_row + 1))];
+        for idx in (0..states.len()).filter(|&idx| states[idx]) {
+            let (above, middle, prefix) = (idx & 31, idx >> 5 & 31, idx >> 10);
+            for below in 0..32 {
+                let row = window_row(rules, out_row, above, middle, below);
+                next[(prefix | row << (3 * out_row)) << 10 | below << 5 | middle] = true;
+            }
+        }
+        states = next;
+    }
+    let mut reachable = vec![false; 1 << 9];
+    for idx in (0..states.len()).filter(|&idx| states[idx]) {
+        reachable[idx >> 10] = true;
+    }
+    reachable
+}
+
+/// Get the 3 cells of window row `out_row` after a tick, as bits, from the
+/// 5-cell input rows above, through and below it.
+fn window_row(
+    rules: [Rule; 2],
+    out_row: usize,
+    above: usize,
+    middle: usize,
+    below: usize,
+) -> usize {
+    let mut row = 0;
+    for col in 0..3 {
+        let live_neighbors = (above >> col & 7).count_ones()
+            + (middle >> col & 5).count_ones()
+            + (below >> col & 7).count_ones();
+        let cell = if middle >> (col + 1) & 1 == 1 {
+            Cell::Alive
+        } else {
+            Cell::Dead
+        };
+        if rules[(out_row + col) % 2].next(cell, live_neighbors as u8) == Cell::Alive {
+            row |= 1 << col;
+        }
+    }
+    row
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "B")?;
+        for n in 0..=8 {
+            if self.birth & (1 << n) != 0 {
+                write!(f, "{}", n)?;
+            }
+        }
+        write!(f, "/S")?;
+        for n in 0..=8 {
+            if self.survival & (1 << n) != 0 {
+                write!(f, "{}", n)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_parse_rules() {
+        assert_eq!(Rule::parse("B3/S23"), Ok(Rule::life()));
+        assert_eq!(Rule::parse(" s23/b3 "), Ok(Rule::life()));
+        assert_eq!(Rule::parse("B36/S23").unwrap().to_string(), "B36/S23");
+        assert_eq!(Rule::parse("B2/S").unwrap().to_string(), "B2/S");
+        assert!(Rule::parse("B9/S23").is_err());
+        assert!(Rule::parse("B3").is_err());
+        assert!(Rule::parse("B3/B3").is_err());
+        assert!(Rule::parse("23/3").is_err());
+    }
+
+    #[test]
+    fn it_should_apply_rules() {
+        let life = Rule::life();
+        assert_eq!(life.next(Cell::Dead, 3), Cell::Alive);
+        assert_eq!(life.next(Cell::Dead, 2), Cell::Dead);
+        assert_eq!(life.next(Cell::Alive, 2), Cell::Alive);
+        assert_eq!(life.next(Cell::Alive, 4), Cell::Dead);
+    }
+
+    #[test]
+    fn it_should_find_reachable_windows() {
+        let life = Rule::life();
+        assert!(reachable_windows([life; 2]).iter().all(|&r| r));
+
+        let no_births = Rule::parse("B/S23").unwrap();
+        let reachable = reachable_windows([no_births; 2]);
+        assert!(!reachable[0b111_111_111]);
+        assert!(reachable[0b000_011_011]);
+        assert!(reachable[0]);
+    }
+}
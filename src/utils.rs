@@ -9,6 +9,7 @@ cfg_if! {
     // https://github.com/rustwasm/console_error_panic_hook#readme
     if #[cfg(feature = "console_error_panic_hook")] {
         extern crate console_error_panic_hook;
+        #[allow(unused_imports)]
         pub use self::console_error_panic_hook::set_once as set_panic_hook;
     } else {
         #[inline]
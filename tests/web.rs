@@ -2,6 +2,7 @@
 
 #![cfg(target_arch = "wasm32")]
 
+extern crate js_sys;
 extern crate wasm_bindgen_test;
 use wasm_bindgen_test::*;
 
@@ -41,3 +42,66 @@ pub fn test_tick() {
     input_universe.tick();
     assert_eq!(&input_universe.get_cells(), &expected_universe.get_cells());
 }
+
+#[wasm_bindgen_test]
+pub fn test_from_grayscale_rejects_wrong_size() {
+    assert!(Universe::from_grayscale(8, 4, &[0; 31], 128).is_err());
+}
+
+#[wasm_bindgen_test]
+pub fn test_open_pattern_rejects_bad_rle() {
+    let mut universe = Universe::new();
+    assert!(universe.open_pattern("3o%!", 2).is_err());
+    assert_eq!(universe.width(), 64);
+}
+
+#[wasm_bindgen_test]
+pub fn test_run_with_callback_survives_throwing_callback() {
+    let mut universe = input_spaceship();
+    let callback =
+        js_sys::Function::new_with_args("generation", "if (generation == 2) throw 'boom';");
+    universe.run_with_callback(4, callback);
+    assert_eq!(universe.generation(), 4);
+}
+
+#[wasm_bindgen_test]
+pub fn test_next_states_for_rules_rejects_bad_rule() {
+    let universe = input_spaceship();
+    let rules = vec!["B3/S23".to_string(), "B3/S2x".to_string()];
+    assert!(universe.next_states_for_rules(&rules).is_err());
+}
+
+#[wasm_bindgen_test]
+pub fn test_rule_distance_rejects_bad_rule() {
+    let mut universe = input_spaceship();
+    assert!(universe.rule_distance("B3/S23", "nope", 3, 10, 5).is_err());
+    assert!(universe.rule_distance("nope", "B3/S23", 3, 10, 5).is_err());
+}
+
+#[wasm_bindgen_test]
+pub fn test_step_by_rejects_irreversible_mode() {
+    let mut universe = input_spaceship();
+    assert!(universe.step_by(-1).is_err());
+    assert!(universe.untick().is_err());
+}
+
+#[wasm_bindgen_test]
+pub fn test_step_by_rejects_going_before_generation_zero() {
+    let mut universe = input_spaceship();
+    universe.set_second_order(true);
+    universe.tick();
+    assert!(universe.step_by(-2).is_err());
+    assert_eq!(universe.generation(), 1);
+    assert!(universe.untick().is_ok());
+    assert!(universe.untick().is_err());
+    assert_eq!(universe.generation(), 0);
+}
+
+#[wasm_bindgen_test]
+pub fn test_open_pattern_rejects_oversized_pattern() {
+    let mut universe = Universe::new();
+    assert!(universe.open_pattern("x = 70000, y = 70000\no!", 0).is_err());
+    assert!(universe.open_pattern("o!", u32::MAX).is_err());
+    assert!(universe.open_pattern("4294967295bo!", 0).is_err());
+    assert_eq!(universe.width(), 64);
+}